@@ -49,7 +49,6 @@ pub fn part_two(input: &str) -> Option<usize> {
     }
 
     Some(stones.len())
-    None
 }
 
 #[cfg(test)]
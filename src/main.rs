@@ -1,144 +1,486 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
-use args::{parse, AppArguments};
+use advent_of_code::template::commands::{
+    all, download, examples, leaderboard, read, scaffold, solve, stars, submissions, time, verify,
+    CommandError,
+};
+use advent_of_code::template::{registry, Year};
+use args::{parse, AppArguments, Invocation};
+use std::process;
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
+use advent_of_code::template::commands::today;
+
+mod solutions;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::cli::{self, Shell};
+    use advent_of_code::template::commands::CommandError;
+    use advent_of_code::template::{registry, timeout, Day, DaySet, Year};
+    use std::{env, time::Duration};
+
+    /// Slowdown in percent above which `time --compare` reports a regression.
+    const DEFAULT_COMPARE_THRESHOLD: f64 = 10.0;
+
+    /// What the binary was asked to do.
+    pub enum Invocation {
+        /// Print text and exit, e.g. help or a completion script.
+        Print(String),
+        Run(Year, AppArguments),
+    }
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            days: DaySet,
+            force: bool,
         },
         Read {
             day: Day,
         },
+        Examples {
+            day: Day,
+            list: bool,
+            part_1: Option<usize>,
+            part_2: Option<usize>,
+            force: bool,
+        },
         Scaffold {
             day: Day,
             download: bool,
             overwrite: bool,
         },
         Solve {
-            day: Day,
+            days: DaySet,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            watch: bool,
+            test: bool,
+            input: Option<String>,
+            example: bool,
         },
         All {
+            days: Option<DaySet>,
             release: bool,
+            timeout: Option<Duration>,
         },
         Time {
             all: bool,
-            day: Option<Day>,
+            days: Option<DaySet>,
             store: bool,
+            compare: Option<f64>,
+            memory: bool,
+            history: Option<Day>,
+            timeout: Option<Duration>,
+        },
+        Submissions {
+            day: Day,
+        },
+        Leaderboard {
+            id: u64,
+            file: Option<String>,
+            cached: bool,
+            day: Option<Day>,
+        },
+        Stars {
+            file: Option<String>,
+            user: Option<u64>,
+        },
+        Verify {
+            days: Option<DaySet>,
+            timeout: Option<Duration>,
+        },
+        RunSolution {
+            day: Day,
+            time: bool,
         },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            wait: bool,
+        },
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
+    /// Parses the command and the year it applies to.
+    pub fn parse() -> Result<Invocation, CommandError> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
+        let subcommand = args.subcommand()?;
+        let wants_help = args.contains(["-h", "--help"]);
+
+        let Some(name) = subcommand else {
+            return Ok(Invocation::Print(cli::overview()));
+        };
+
+        // the internal command used by `run_multi` is not part of the spec.
+        let spec = if name == registry::RUN_COMMAND {
+            None
+        } else {
+            Some(cli::command(&name).ok_or_else(|| {
+                CommandError::Usage(format!(
+                    "Unknown command `{name}`. Run `cargo run -- help` to list all commands."
+                ))
+            })?)
+        };
+
+        let with_usage = |message: String| match spec {
+            Some(spec) => CommandError::Usage(format!("{message}\n\n{}", cli::short_usage(spec))),
+            None => CommandError::Usage(message),
+        };
+
+        if let Some(spec) = spec {
+            if wants_help {
+                return Ok(Invocation::Print(cli::help(spec)));
+            }
+
+            // the spec is what help and completions show, so options that are not in it are rejected.
+            let raw_args: Vec<String> = env::args().skip(2).collect();
+            if let Some(option) = cli::unknown_option(spec, &raw_args) {
+                return Err(with_usage(format!("Unknown option `{option}`.")));
+            }
+        }
+
+        let invocation = match name.as_str() {
+            "help" => {
+                let command: Option<String> = args.opt_free_from_str()?;
+                let text = match command {
+                    Some(command) => cli::help(cli::command(&command).ok_or_else(|| {
+                        CommandError::Usage(format!("Unknown command `{command}`."))
+                    })?),
+                    None => cli::overview(),
+                };
+                Invocation::Print(text)
+            }
+            "completions" => {
+                let shell: Shell = args
+                    .free_from_str()
+                    .map_err(|e| with_usage(e.to_string()))?;
+                Invocation::Print(cli::completions(shell))
+            }
+            _ => {
+                // NOTE: options need to be parsed before free arguments.
+                let year = args
+                    .opt_value_from_str("--year")?
+                    .or_else(Year::from_env)
+                    .ok_or_else(|| {
+                        CommandError::Usage(
+                            "No year specified. Pass `--year <year>` or set AOC_YEAR in .cargo/config.toml."
+                                .into(),
+                        )
+                    })?;
+
+                let app_args =
+                    parse_command(&name, &mut args).map_err(|e| with_usage(e.to_string()))?;
+
+                Invocation::Run(year, app_args)
+            }
+        };
+
+        let remaining = args.finish();
+        if !remaining.is_empty() {
+            let remaining: Vec<String> = remaining
+                .iter()
+                .map(|x| format!("`{}`", x.to_string_lossy()))
+                .collect();
+            return Err(with_usage(format!(
+                "Unknown argument(s) {}.",
+                remaining.join(", ")
+            )));
+        }
+
+        Ok(invocation)
+    }
+
+    /// Parses the arguments of a command that runs for a year.
+    pub(crate) fn parse_command(
+        name: &str,
+        args: &mut pico_args::Arguments,
+    ) -> Result<AppArguments, pico_args::Error> {
+        Ok(match name {
+            "all" => AppArguments::All {
                 release: args.contains("--release"),
+                timeout: parse_timeout(args)?,
+                days: args.opt_free_from_str()?,
             },
-            Some("time") => {
+            "time" => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let memory = args.contains("--memory");
+                let compare = if args.contains("--compare") {
+                    Some(
+                        args.opt_value_from_str("--threshold")?
+                            .unwrap_or(DEFAULT_COMPARE_THRESHOLD),
+                    )
+                } else {
+                    None
+                };
 
                 AppArguments::Time {
                     all,
-                    day: args.opt_free_from_str()?,
+                    history: args.opt_value_from_str("--history")?,
                     store,
+                    compare,
+                    memory,
+                    timeout: parse_timeout(args)?,
+                    days: args.opt_free_from_str()?,
                 }
             }
-            Some("download") => AppArguments::Download {
+            "download" => AppArguments::Download {
+                force: args.contains("--force"),
+                days: args.free_from_str()?,
+            },
+            "read" => AppArguments::Read {
                 day: args.free_from_str()?,
             },
-            Some("read") => AppArguments::Read {
+            "examples" => AppArguments::Examples {
+                list: args.contains("--list"),
+                part_1: args.opt_value_from_str("--part1")?,
+                part_2: args.opt_value_from_str("--part2")?,
+                force: args.contains("--force"),
                 day: args.free_from_str()?,
             },
-            Some("scaffold") => AppArguments::Scaffold {
+            "scaffold" => AppArguments::Scaffold {
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+            "solve" => AppArguments::Solve {
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                watch: args.contains("--watch"),
+                test: args.contains("--test"),
+                input: args.opt_value_from_str("--input")?,
+                example: args.contains("--example"),
+                days: args.free_from_str()?,
+            },
+            "submissions" => AppArguments::Submissions {
+                day: args.free_from_str()?,
+            },
+            "leaderboard" => AppArguments::Leaderboard {
+                file: args.opt_value_from_str("--file")?,
+                cached: args.contains("--cached"),
+                day: args.opt_value_from_str("--day")?,
+                id: args.free_from_str()?,
+            },
+            "stars" => AppArguments::Stars {
+                file: args.opt_value_from_str("--file")?,
+                user: args.opt_value_from_str("--user")?,
+            },
+            "verify" => AppArguments::Verify {
+                timeout: parse_timeout(args)?,
+                days: args.opt_free_from_str()?,
             },
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
-            }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
-            }
-        };
-
-        let remaining = args.finish();
-        if !remaining.is_empty() {
-            eprintln!("Warning: unknown argument(s): {remaining:?}.");
-        }
+            "today" => AppArguments::Today {
+                wait: args.contains("--wait"),
+            },
+            registry::RUN_COMMAND => AppArguments::RunSolution {
+                time: args.contains("--time"),
+                day: args.free_from_str()?,
+            },
+            x => unreachable!("command `{x}` is in the spec, but not parsed"),
+        })
+    }
 
-        Ok(app_args)
+    /// Reads `--timeout`, falling back to the timeout configured in the environment.
+    fn parse_timeout(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<Duration>, pico_args::Error> {
+        Ok(args
+            .opt_value_from_fn("--timeout", timeout::parse)?
+            .unwrap_or_else(timeout::from_env))
     }
 }
 
 fn main() {
-    match parse() {
-        Err(err) => {
+    registry::register(solutions::SOLUTIONS);
+
+    let result = parse().and_then(|invocation| match invocation {
+        Invocation::Print(text) => {
+            println!("{text}");
+            Ok(())
+        }
+        Invocation::Run(year, args) => run(year, args),
+    });
+
+    if let Err(err) = result {
+        // a failed solution already printed its own error.
+        if !matches!(err, CommandError::Solution(_)) {
             eprintln!("Error: {err}");
-            std::process::exit(1);
         }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
-                day,
-                download,
-                overwrite,
-            } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
-                }
+        process::exit(err.exit_code());
+    }
+}
+
+fn run(year: Year, args: AppArguments) -> Result<(), CommandError> {
+    match args {
+        AppArguments::All {
+            days,
+            release,
+            timeout,
+        } => all::handle(year, days, release, timeout),
+        AppArguments::Time {
+            history: Some(day),
+            days: None,
+            all: false,
+            store: false,
+            compare: None,
+            memory: false,
+            ..
+        } => {
+            time::handle_history(year, day);
+            Ok(())
+        }
+        AppArguments::Time {
+            history: Some(_), ..
+        } => Err(CommandError::Usage(
+            "`--history` can not be combined with days or other options.".into(),
+        )),
+        AppArguments::Time {
+            days,
+            all,
+            store,
+            compare,
+            memory,
+            history: None,
+            timeout,
+        } => time::handle(year, days, all, store, compare, memory, timeout),
+        AppArguments::Submissions { day } => submissions::handle(year, day),
+        AppArguments::Leaderboard {
+            id,
+            file,
+            cached,
+            day,
+        } => leaderboard::handle(year, id, file, cached, day),
+        AppArguments::Stars { file, user } => stars::handle(year, file, user),
+        AppArguments::Verify { days, timeout } => verify::handle(year, days, timeout),
+        AppArguments::RunSolution { day, time } => {
+            if registry::run_reporting(year, day, time) {
+                Ok(())
+            } else {
+                Err(CommandError::Data(format!(
+                    "Day {day} of {year} is not registered in src/solutions.rs."
+                )))
             }
-            AppArguments::Solve {
-                day,
-                release,
-                dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
+        }
+        AppArguments::Download { days, force } => {
+            for day in days.resolve(year) {
+                download::handle(year, day, force)?;
+            }
+            Ok(())
+        }
+        AppArguments::Read { day } => read::handle(year, day),
+        AppArguments::Examples {
+            day,
+            list,
+            part_1,
+            part_2,
+            force,
+        } => examples::handle(year, day, list, [part_1, part_2], force),
+        AppArguments::Scaffold {
+            day,
+            download,
+            overwrite,
+        } => {
+            scaffold::handle(year, day, overwrite)?;
+            if download {
+                download::handle(year, day, false)?;
+                scaffold::fill_expected_answers(year, day)?;
             }
+            Ok(())
+        }
+        AppArguments::Solve {
+            days,
+            release,
+            dhat,
+            submit,
+            input,
+            example,
+            watch: false,
+            ..
+        } => solve::handle(
+            year,
+            &days,
+            release,
+            dhat,
+            submit,
+            input.as_deref(),
+            example,
+        ),
+        AppArguments::Solve {
+            days,
+            release,
+            test,
+            input: None,
+            example: false,
+            watch: true,
+            ..
+        } => match days.resolve(year)[..] {
+            [day] => solve::watch(year, day, release, test),
+            _ => Err(CommandError::Usage(
+                "`--watch` only works with a single day.".into(),
+            )),
         },
-    };
+        AppArguments::Solve { .. } => Err(CommandError::Usage(
+            "`--watch` can not be combined with `--input` or `--example`.".into(),
+        )),
+        #[cfg(feature = "today")]
+        AppArguments::Today { wait } => today::handle(year, wait),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::args::parse_command;
+    use advent_of_code::template::cli::{self, Arg, COMMANDS};
+    use std::ffi::OsString;
+
+    /// A valid value for an argument of the spec, by the name of its value.
+    fn sample_value(value: &str) -> &'static str {
+        match value {
+            "duration" => "10s",
+            "path" => "data/leaderboard.json",
+            "year" => "2024",
+            _ => "1",
+        }
+    }
+
+    #[test]
+    fn parses_every_option_of_the_spec() {
+        for command in COMMANDS {
+            // `help` and `completions` are handled before commands that run for a year.
+            if matches!(command.name, "help" | "completions") {
+                continue;
+            }
+
+            let mut argv: Vec<&str> = vec![];
+
+            for arg in command.args {
+                match arg {
+                    Arg::Free { name, .. } => argv.push(sample_value(name)),
+                    Arg::Flag { name, .. } => argv.push(name),
+                    Arg::Value { name, value, .. } => {
+                        argv.extend([*name, sample_value(value)]);
+                    }
+                }
+            }
+
+            let mut args =
+                pico_args::Arguments::from_vec(argv.iter().map(OsString::from).collect());
+
+            assert!(
+                parse_command(command.name, &mut args).is_ok(),
+                "`{}` does not parse {argv:?}",
+                command.name
+            );
+
+            let remaining = args.finish();
+            assert!(
+                remaining.is_empty(),
+                "`{}` does not accept {remaining:?}",
+                command.name
+            );
+
+            let raw_args: Vec<String> = argv.iter().map(|x| x.to_string()).collect();
+            assert_eq!(cli::unknown_option(command, &raw_args), None);
+        }
+    }
 }
@@ -0,0 +1,16 @@
+// Solutions that `cargo all`, `cargo time` and `cargo verify` compile into the main binary. `cargo scaffold` adds new days here.
+// They are only compiled with the `registry` feature, so a broken solution does not break the other commands.
+advent_of_code::register_solutions! {
+    day_01 => "bin/01.rs",
+    day_02 => "bin/02.rs",
+    day_03 => "bin/03.rs",
+    day_04 => "bin/04.rs",
+    day_05 => "bin/05.rs",
+    day_06 => "bin/06.rs",
+    day_07 => "bin/07.rs",
+    day_08 => "bin/08.rs",
+    day_09 => "bin/09.rs",
+    day_10 => "bin/10.rs",
+    day_11 => "bin/11.rs",
+    day_13 => "bin/13.rs",
+}
@@ -0,0 +1,218 @@
+/// Store of accepted answers, used to check that solutions keep producing the right results on real inputs.
+/// Answers are recorded when a submission is accepted, but the file can also be edited by hand:
+///
+/// ```json
+/// { "01": { "part_1": "11", "part_2": "31" } }
+/// ```
+use std::{collections::HashMap, fs, io::Error, str::FromStr};
+
+use tinyjson::JsonValue;
+
+use crate::template::{Day, Year};
+
+static ANSWERS_FILE_NAME: &str = "answers.json";
+
+/// Accepted answers for a set of days.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Answers {
+    data: HashMap<Day, [Option<String>; 2]>,
+}
+
+/// Outcome of checking an answer against the store.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Verification {
+    Match,
+    Mismatch {
+        expected: String,
+    },
+    /// No answer has been recorded for this part yet.
+    Missing,
+}
+
+impl Answers {
+    /// Dehydrate answers to the JSON file of a year.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(answers_path(year))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from the JSON file of a year. If not present, returns empty answers.
+    pub fn read_from_file(year: Year) -> Self {
+        fs::read_to_string(answers_path(year))
+            .map_err(|x| x.to_string())
+            .and_then(|s| Answers::from_str(&s))
+            .unwrap_or_default()
+    }
+
+    /// Returns the accepted answer for a part, if any.
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        self.data
+            .get(&day)?
+            .get(usize::from(part).checked_sub(1)?)?
+            .as_deref()
+    }
+
+    /// Records the accepted answer for a part. Parts other than 1 and 2 are ignored.
+    pub fn set(&mut self, day: Day, part: u8, answer: &str) {
+        let Some(index) = usize::from(part).checked_sub(1).filter(|i| *i < 2) else {
+            return;
+        };
+
+        self.data.entry(day).or_default()[index] = Some(answer.to_string());
+    }
+
+    /// Days that have at least one recorded answer, sorted.
+    pub fn days(&self) -> Vec<Day> {
+        let mut days: Vec<Day> = self.data.keys().copied().collect();
+        days.sort_unstable();
+        days
+    }
+
+    /// Checks an answer produced by a solution against the recorded one.
+    pub fn verify(&self, day: Day, part: u8, answer: Option<&str>) -> Verification {
+        match self.get(day, part) {
+            None => Verification::Missing,
+            Some(expected) if answer == Some(expected) => Verification::Match,
+            Some(expected) => Verification::Mismatch {
+                expected: expected.to_string(),
+            },
+        }
+    }
+}
+
+/// Records an accepted answer in the answers file.
+pub fn record(year: Year, day: Day, part: u8, answer: &str) -> Result<(), Error> {
+    let mut answers = Answers::read_from_file(year);
+    answers.set(day, part, answer);
+    answers.store_file(year)
+}
+
+fn answers_path(year: Year) -> String {
+    format!("{}/{ANSWERS_FILE_NAME}", year.data_dir())
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answers> for JsonValue {
+    fn from(value: &Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        for (day, parts) in &value.data {
+            let mut day_map: HashMap<String, JsonValue> = HashMap::new();
+
+            for (key, answer) in ["part_1", "part_2"].iter().zip(parts) {
+                if let Some(answer) = answer {
+                    day_map.insert((*key).into(), JsonValue::String(answer.clone()));
+                }
+            }
+
+            map.insert(day.to_string(), JsonValue::Object(day_map));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl FromStr for Answers {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answers to be a JSON object.")?;
+
+        let mut data = HashMap::new();
+
+        for (key, value) in json {
+            let day = Day::from_str(key).or(Err(format!("Expected `{key}` to be a day.")))?;
+
+            let parts = value
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or(format!("Expected answers.{key} to be a JSON object."))?;
+
+            // NOTE: answers are strings, but numbers are accepted to ease editing the file by hand.
+            let answer = |part: &str| match parts.get(part) {
+                None | Some(JsonValue::Null) => Ok(None),
+                Some(JsonValue::String(s)) => Ok(Some(s.clone())),
+                Some(JsonValue::Number(n)) => Ok(Some(n.to_string())),
+                Some(_) => Err(format!(
+                    "Expected answers.{key}.{part} to be null, string or number."
+                )),
+            };
+
+            data.insert(day, [answer("part_1")?, answer("part_2")?]);
+        }
+
+        Ok(Answers { data })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answers, Verification};
+    use crate::day;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn parses_answers() {
+        let answers: Answers = r#"{"01":{"part_1":"11","part_2":31},"03":{"part_1":null}}"#
+            .parse()
+            .unwrap();
+
+        assert_eq!(answers.get(day!(1), 1), Some("11"));
+        assert_eq!(answers.get(day!(1), 2), Some("31"));
+        assert_eq!(answers.get(day!(3), 1), None);
+        assert_eq!(answers.days(), vec![day!(1), day!(3)]);
+    }
+
+    #[test]
+    fn rejects_invalid_days() {
+        assert!(r#"{"26":{"part_1":"11"}}"#.parse::<Answers>().is_err());
+    }
+
+    #[test]
+    fn roundtrips_answers() {
+        let mut answers = Answers::default();
+        answers.set(day!(5), 2, "1234");
+
+        let json = JsonValue::from(&answers).stringify().unwrap();
+        assert_eq!(json.parse::<Answers>().unwrap(), answers);
+    }
+
+    #[test]
+    fn ignores_invalid_parts() {
+        let mut answers = Answers::default();
+        answers.set(day!(5), 0, "1234");
+        answers.set(day!(5), 3, "1234");
+
+        assert_eq!(answers.get(day!(5), 1), None);
+        assert_eq!(answers.get(day!(5), 0), None);
+        assert!(answers.days().is_empty());
+    }
+
+    #[test]
+    fn verifies_answers() {
+        let mut answers = Answers::default();
+        answers.set(day!(2), 1, "42");
+
+        assert_eq!(answers.verify(day!(2), 1, Some("42")), Verification::Match);
+        assert_eq!(
+            answers.verify(day!(2), 1, Some("43")),
+            Verification::Mismatch {
+                expected: "42".into()
+            }
+        );
+        assert_eq!(
+            answers.verify(day!(2), 1, None),
+            Verification::Mismatch {
+                expected: "42".into()
+            }
+        );
+        assert_eq!(answers.verify(day!(2), 2, Some("1")), Verification::Missing);
+    }
+}
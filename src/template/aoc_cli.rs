@@ -1,31 +1,76 @@
-/// Wrapper module around the "aoc-cli" command-line.
+/// Access to the Advent of Code website, used by the `download`, `read` and `solve --submit` commands.
+/// Requests go through the built-in [`Client`] by default. Setting `AOC_BACKEND=aoc-cli` wraps the "aoc-cli" command-line instead.
 use std::{
+    env,
     fmt::Display,
+    fs, io,
     process::{Command, Output, Stdio},
 };
 
-use crate::template::Day;
+use crate::template::aoc_client::{Client, ClientError};
+use crate::template::request_gate::{Endpoint, RequestGate};
+use crate::template::submissions::cooldown;
+use crate::template::{Day, Year};
+
+pub const BACKEND_ENV: &str = "AOC_BACKEND";
 
 #[derive(Debug)]
 pub enum AocCommandError {
     CommandNotFound,
     CommandNotCallable,
     BadExitStatus(Output),
+    Client(ClientError),
+    IO(io::Error),
 }
 
 impl Display for AocCommandError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            AocCommandError::CommandNotFound => write!(f, "aoc-cli is not present in environment."),
+            AocCommandError::CommandNotFound => write!(f, "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."),
             AocCommandError::CommandNotCallable => write!(f, "aoc-cli could not be called."),
             AocCommandError::BadExitStatus(_) => {
                 write!(f, "aoc-cli exited with a non-zero status.")
             }
+            AocCommandError::Client(e) => write!(f, "{e}"),
+            AocCommandError::IO(e) => write!(f, "{e}"),
         }
     }
 }
 
-pub fn check() -> Result<(), AocCommandError> {
+impl AocCommandError {
+    /// Returns `true` for failed requests that may succeed when tried again later, e.g. an input that is not available yet.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            AocCommandError::BadExitStatus(_) | AocCommandError::Client(ClientError::Http(_))
+        )
+    }
+}
+
+impl From<ClientError> for AocCommandError {
+    fn from(e: ClientError) -> Self {
+        AocCommandError::Client(e)
+    }
+}
+
+impl From<io::Error> for AocCommandError {
+    fn from(e: io::Error) -> Self {
+        AocCommandError::IO(e)
+    }
+}
+
+/// Returns `true` if requests should be made via aoc-cli instead of the built-in client.
+fn use_aoc_cli() -> bool {
+    env::var(BACKEND_ENV).is_ok_and(|x| x == "aoc-cli")
+}
+
+/// Checks that the configured backend is usable, i.e. a session is set up or aoc-cli is installed.
+pub fn check(year: Year) -> Result<(), AocCommandError> {
+    if !use_aoc_cli() {
+        Client::from_env(year)?;
+        return Ok(());
+    }
+
     Command::new("aoc")
         .arg("-V")
         .output()
@@ -33,8 +78,15 @@ pub fn check() -> Result<(), AocCommandError> {
     Ok(())
 }
 
-pub fn read(day: Day) -> Result<Output, AocCommandError> {
-    let puzzle_path = get_puzzle_path(day);
+pub fn read(year: Year, day: Day) -> Result<(), AocCommandError> {
+    let puzzle_path = year.puzzle_path(day);
+
+    if !use_aoc_cli() {
+        let puzzle = Client::from_env(year)?.puzzle(day)?;
+        fs::write(&puzzle_path, &puzzle)?;
+        println!("{puzzle}");
+        return Ok(());
+    }
 
     let args = build_args(
         "read",
@@ -43,76 +95,111 @@ pub fn read(day: Day) -> Result<Output, AocCommandError> {
             "--puzzle-file".into(),
             puzzle_path,
         ],
+        year,
         day,
     );
 
-    call_aoc_cli(&args)
+    throttle(&[Endpoint::Puzzle])?;
+    call_aoc_cli(&args)?;
+    Ok(())
 }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
-    let puzzle_path = get_puzzle_path(day);
-
-    let args = build_args(
-        "download",
-        &[
-            "--overwrite".into(),
-            "--input-file".into(),
-            input_path.to_string(),
-            "--puzzle-file".into(),
-            puzzle_path.to_string(),
-        ],
-        day,
-    );
+pub fn download(year: Year, day: Day) -> Result<(), AocCommandError> {
+    let input_path = year.data_path("inputs", day);
+    let puzzle_path = year.puzzle_path(day);
+
+    if use_aoc_cli() {
+        let args = build_args(
+            "download",
+            &[
+                "--overwrite".into(),
+                "--input-file".into(),
+                input_path.to_string(),
+                "--puzzle-file".into(),
+                puzzle_path.to_string(),
+            ],
+            year,
+            day,
+        );
+
+        throttle(&[Endpoint::Input, Endpoint::Puzzle])?;
+        call_aoc_cli(&args)?;
+    } else {
+        let mut client = Client::from_env(year)?;
+        fs::write(&input_path, client.input(day)?)?;
+        fs::write(&puzzle_path, client.puzzle(day)?)?;
+    }
 
-    let output = call_aoc_cli(&args)?;
     println!("---");
     println!("🎄 Successfully wrote input to \"{}\".", &input_path);
     println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
-    Ok(output)
+    Ok(())
 }
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
+/// Submits an answer and returns the response text, which tells whether the answer was accepted.
+pub fn submit(year: Year, day: Day, part: u8, result: &str) -> Result<String, AocCommandError> {
+    if !use_aoc_cli() {
+        let response = Client::from_env(year)?.submit(day, part, result)?;
+        println!("{response}");
+        return Ok(response);
+    }
+
     // workaround: the argument order is inverted for submit.
-    let mut args = build_args("submit", &[], day);
+    let mut args = build_args("submit", &[], year, day);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
-}
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
-}
+    // capture the response so callers can tell whether the answer was accepted.
+    throttle(&[Endpoint::Answer])?;
+    let output = call_aoc_cli_with(&args, Stdio::piped())?;
+    let response = String::from_utf8_lossy(&output.stdout).to_string();
+    print!("{response}");
+
+    if let Some(wait) = cooldown(&response) {
+        RequestGate::from_env().back_off(Endpoint::Answer, wait);
+    }
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+    Ok(response)
 }
 
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
+/// Passes the request gate for the endpoints aoc-cli is about to request. The built-in client does this on its own.
+fn throttle(endpoints: &[Endpoint]) -> Result<(), AocCommandError> {
+    let mut gate = RequestGate::from_env();
+
+    for endpoint in endpoints {
+        gate.acquire(*endpoint)
+            .map_err(|wait| ClientError::Throttled {
+                endpoint: *endpoint,
+                wait,
+            })?;
     }
+
+    Ok(())
 }
 
-fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
+fn build_args(command: &str, args: &[String], year: Year, day: Day) -> Vec<String> {
     let mut cmd_args = args.to_vec();
 
-    if let Some(year) = get_year() {
-        cmd_args.push("--year".into());
-        cmd_args.push(year.to_string());
-    }
-
-    cmd_args.append(&mut vec!["--day".into(), day.to_string(), command.into()]);
+    cmd_args.append(&mut vec![
+        "--year".into(),
+        year.to_string(),
+        "--day".into(),
+        day.to_string(),
+        command.into(),
+    ]);
 
     cmd_args
 }
 
 fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
+    call_aoc_cli_with(args, Stdio::inherit())
+}
+
+fn call_aoc_cli_with(args: &[String], stdout: Stdio) -> Result<Output, AocCommandError> {
     // println!("Calling >aoc with: {}", args.join(" "));
     let output = Command::new("aoc")
         .args(args)
-        .stdout(Stdio::inherit())
+        .stdout(stdout)
         .stderr(Stdio::inherit())
         .output()
         .map_err(|_| AocCommandError::CommandNotCallable)?;
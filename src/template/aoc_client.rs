@@ -0,0 +1,418 @@
+/// Built-in client for the Advent of Code website. Fetches inputs and puzzle descriptions and submits answers.
+/// The session cookie is read from `AOC_SESSION` or from the file `~/.adventofcode.session` that aoc-cli uses.
+/// Requests go to `AOC_BASE_URL` if set, which allows pointing the client at a local mock server.
+/// Every request passes the shared [`RequestGate`] and identifies itself with the `AOC_USER_AGENT`.
+use std::{env, fmt::Display, fs, path::PathBuf, time::Duration};
+
+use crate::template::request_gate::{Endpoint, RequestGate};
+use crate::template::submissions::cooldown;
+use crate::template::{Day, Year};
+
+pub const SESSION_ENV: &str = "AOC_SESSION";
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+pub const USER_AGENT_ENV: &str = "AOC_USER_AGENT";
+
+static DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+static DEFAULT_USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+static SESSION_FILE_NAME: &str = ".adventofcode.session";
+
+#[derive(Debug)]
+pub enum ClientError {
+    MissingSession,
+    Throttled { endpoint: Endpoint, wait: Duration },
+    Http(String),
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set {SESSION_ENV} or create ~/{SESSION_FILE_NAME}."
+            ),
+            ClientError::Throttled { endpoint, wait } => write!(
+                f,
+                "too many {endpoint} requests, try again in {}s.",
+                wait.as_secs().max(1)
+            ),
+            ClientError::Http(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// Client for a single advent of code year, authenticated with a session cookie.
+pub struct Client {
+    base_url: String,
+    session: String,
+    year: Year,
+    agent: ureq::Agent,
+    gate: RequestGate,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str, year: Year) -> Self {
+        let user_agent = env::var(USER_AGENT_ENV)
+            .ok()
+            .filter(|x| !x.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_USER_AGENT.into());
+
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            year,
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .user_agent(&user_agent)
+                .build(),
+            gate: RequestGate::from_env(),
+        }
+    }
+
+    /// Replaces the request gate, e.g. with [`RequestGate::offline`] in tests.
+    pub fn with_gate(mut self, gate: RequestGate) -> Self {
+        self.gate = gate;
+        self
+    }
+
+    /// Creates a client for a year from the session and base url configured in the environment.
+    pub fn from_env(year: Year) -> Result<Self, ClientError> {
+        let base_url = env::var(BASE_URL_ENV).unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        let session = read_session().ok_or(ClientError::MissingSession)?;
+
+        Ok(Self::new(&base_url, &session, year))
+    }
+
+    /// Fetches the puzzle input of a day.
+    pub fn input(&mut self, day: Day) -> Result<String, ClientError> {
+        self.get(Endpoint::Input, &format!("{}/input", self.day_url(day)))
+    }
+
+    /// Fetches the puzzle description of a day, converted to markdown.
+    pub fn puzzle(&mut self, day: Day) -> Result<String, ClientError> {
+        let html = self.get(Endpoint::Puzzle, &self.day_url(day))?;
+        Ok(html_to_markdown(&html))
+    }
+
+    /// Submits an answer and returns the text of the server's response.
+    /// If the server asks to wait before the next submission, further submissions are held back until then.
+    pub fn submit(&mut self, day: Day, part: u8, answer: &str) -> Result<String, ClientError> {
+        let url = format!("{}/answer", self.day_url(day));
+        self.throttle(Endpoint::Answer)?;
+
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+
+        let html = read_response(&url, response)?;
+        let text = article_text(&html);
+
+        if let Some(wait) = cooldown(&text) {
+            self.gate.back_off(Endpoint::Answer, wait);
+        }
+
+        Ok(text)
+    }
+
+    /// Fetches the JSON of a private leaderboard.
+    pub fn leaderboard(&mut self, id: u64) -> Result<String, ClientError> {
+        let url = format!(
+            "{}/{}/leaderboard/private/view/{id}.json",
+            self.base_url, self.year
+        );
+        self.get(Endpoint::Leaderboard, &url)
+    }
+
+    fn day_url(&self, day: Day) -> String {
+        format!("{}/{}/day/{}", self.base_url, self.year, day.into_inner())
+    }
+
+    fn throttle(&mut self, endpoint: Endpoint) -> Result<(), ClientError> {
+        self.gate
+            .acquire(endpoint)
+            .map_err(|wait| ClientError::Throttled { endpoint, wait })
+    }
+
+    fn get(&mut self, endpoint: Endpoint, url: &str) -> Result<String, ClientError> {
+        self.throttle(endpoint)?;
+
+        let response = self
+            .agent
+            .get(url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call();
+
+        read_response(url, response)
+    }
+}
+
+fn read_response(
+    url: &str,
+    response: Result<ureq::Response, ureq::Error>,
+) -> Result<String, ClientError> {
+    match response {
+        Ok(response) => response
+            .into_string()
+            .map_err(|e| ClientError::Http(format!("could not read response of {url}: {e}"))),
+        Err(ureq::Error::Status(code, _)) => Err(ClientError::Http(format!(
+            "{url} responded with status {code}."
+        ))),
+        Err(e) => Err(ClientError::Http(format!("request to {url} failed: {e}"))),
+    }
+}
+
+/// Reads the session cookie from the environment, falling back to the session file in the home directory.
+fn read_session() -> Option<String> {
+    if let Ok(session) = env::var(SESSION_ENV) {
+        return Some(session).filter(|s| !s.trim().is_empty());
+    }
+
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?;
+    fs::read_to_string(PathBuf::from(home).join(SESSION_FILE_NAME))
+        .ok()
+        .filter(|s| !s.trim().is_empty())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Converts the puzzle articles of a day page to markdown.
+/// Only the handful of tags that puzzle descriptions use are supported, others are dropped.
+pub fn html_to_markdown(html: &str) -> String {
+    let mut out = String::new();
+
+    for article in articles(html) {
+        let mut rest = article;
+        let mut in_pre = false;
+        let mut links: Vec<String> = vec![];
+
+        while let Some(start) = rest.find('<') {
+            out.push_str(&decode_entities(&rest[..start]));
+
+            let Some(end) = rest[start..].find('>') else {
+                break;
+            };
+
+            let tag = &rest[start + 1..start + end];
+            rest = &rest[start + end + 1..];
+
+            let name = tag
+                .split_whitespace()
+                .next()
+                .unwrap_or_default()
+                .to_ascii_lowercase();
+
+            match name.as_str() {
+                "h2" => out.push_str("## "),
+                "/h2" | "/p" | "/ul" => out.push_str("\n\n"),
+                "pre" => {
+                    in_pre = true;
+                    out.push_str("```\n");
+                }
+                "/pre" => {
+                    in_pre = false;
+                    if !out.ends_with('\n') {
+                        out.push('\n');
+                    }
+                    out.push_str("```\n\n");
+                }
+                "code" | "/code" if !in_pre => out.push('`'),
+                // example blocks highlight parts with `em`, which would end up in the extracted examples.
+                "em" | "/em" if !in_pre => out.push('*'),
+                "li" => out.push_str("- "),
+                "/li" | "br" | "br/" => out.push('\n'),
+                "a" => {
+                    links.push(attribute(tag, "href").unwrap_or_default());
+                    out.push('[');
+                }
+                "/a" => {
+                    out.push_str(&format!("]({})", links.pop().unwrap_or_default()));
+                }
+                _ => {}
+            }
+        }
+
+        out.push_str(&decode_entities(rest));
+    }
+
+    out.trim_end().to_string() + "\n"
+}
+
+/// Text content of the first article of a page, which holds the message after submitting an answer.
+fn article_text(html: &str) -> String {
+    let article = articles(html).next().unwrap_or(html);
+
+    let mut text = String::new();
+    let mut in_tag = false;
+
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    decode_entities(&text)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Contents of all `<article>` elements of a page.
+fn articles(html: &str) -> impl Iterator<Item = &str> {
+    html.split("<article").skip(1).filter_map(|chunk| {
+        let content = &chunk[chunk.find('>')? + 1..];
+        Some(&content[..content.find("</article>").unwrap_or(content.len())])
+    })
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let start = tag.find(&format!("{name}=\""))? + name.len() + 2;
+    let end = tag[start..].find('"')?;
+    Some(decode_entities(&tag[start..start + end]))
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{article_text, html_to_markdown, Client, ClientError, RequestGate};
+    use crate::{day, year};
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+    };
+
+    /// Serves the given bodies to consecutive requests and sends back every received request.
+    fn mock_server(bodies: Vec<&'static str>) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            for body in bodies {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request = String::new();
+                let mut content_length = 0;
+
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(length) = line.to_ascii_lowercase().strip_prefix("content-length:")
+                    {
+                        content_length = length.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+
+                let mut content = vec![0; content_length];
+                reader.read_exact(&mut content).unwrap();
+                request.push_str(&String::from_utf8(content).unwrap());
+                tx.send(request).unwrap();
+
+                write!(
+                    reader.get_mut(),
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+
+        (url, rx)
+    }
+
+    #[test]
+    fn fetches_inputs() {
+        let (url, requests) = mock_server(vec!["1 2 3\n"]);
+        let mut client = Client::new(&url, "secret", year!(2024)).with_gate(RequestGate::offline());
+
+        assert_eq!(client.input(day!(6)).unwrap(), "1 2 3\n");
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /2024/day/6/input "));
+        assert!(request.contains("session=secret"));
+        assert!(request
+            .to_lowercase()
+            .contains("user-agent: github.com/fspoettel/advent-of-code-rust"));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (url, requests) = mock_server(vec![
+            "<main><article><p>That's the right answer! <a href=\"/2024\">[Return]</a></p></article></main>",
+        ]);
+        let mut client = Client::new(&url, "secret", year!(2024)).with_gate(RequestGate::offline());
+
+        assert_eq!(
+            client.submit(day!(1), 2, "42").unwrap(),
+            "That's the right answer! [Return]"
+        );
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /2024/day/1/answer "));
+        assert!(request.ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn holds_back_submissions_when_asked_to_wait() {
+        let (url, requests) = mock_server(vec![
+            "<main><article><p>That's not the right answer. Please wait 5 minutes before trying again.</p></article></main>",
+        ]);
+        let mut client = Client::new(&url, "secret", year!(2024)).with_gate(RequestGate::offline());
+
+        assert!(client.submit(day!(1), 1, "42").is_ok());
+        assert!(matches!(
+            client.submit(day!(1), 1, "43"),
+            Err(ClientError::Throttled { .. })
+        ));
+
+        requests.recv().unwrap();
+        assert!(requests.try_recv().is_err());
+    }
+
+    #[test]
+    fn converts_puzzles_to_markdown() {
+        let html = "<body><article class=\"day-desc\"><h2>--- Day 1: Test ---</h2>\
+            <p>Read <a href=\"/about\">this</a> &amp; find <code>x &lt; y</code>:</p>\
+            <pre><code>1 2\n3 4\n</code></pre><ul><li>total is <code><em>11</em></code></li></ul>\
+            </article></body>";
+
+        assert_eq!(
+            html_to_markdown(html),
+            "## --- Day 1: Test ---\n\nRead [this](/about) & find `x < y`:\n\n```\n1 2\n3 4\n```\n\n- total is `*11*`\n"
+        );
+    }
+
+    #[test]
+    fn drops_highlights_in_code_blocks() {
+        let html = "<article><pre><code>a<em>b</em>c</code></pre></article>";
+        assert_eq!(html_to_markdown(html), "```\nabc\n```\n");
+    }
+
+    #[test]
+    fn extracts_article_text() {
+        assert_eq!(
+            article_text("<article><p>You gave an answer too recently;\n you have <em>30s</em> left.</p></article>"),
+            "You gave an answer too recently; you have 30s left."
+        );
+    }
+}
@@ -0,0 +1,708 @@
+/// Spec of the commands of the main binary, used to print help and to generate shell completions.
+/// Arguments are parsed with `pico_args` in `main.rs`. Options that are not in this table are rejected before
+/// parsing, and a test in `main.rs` checks that the parser accepts every option of every command in it.
+use std::{fmt::Write, str::FromStr};
+
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+/// Name of the shell function that completes the commands, which are run as cargo aliases.
+const COMPLETION_FN: &str = "_advent_of_code_cargo";
+
+pub struct Command {
+    pub name: &'static str,
+    pub about: &'static str,
+    pub args: &'static [Arg],
+}
+
+pub enum Arg {
+    /// A free argument, e.g. `<day>`. Optional ones are printed as `[<day>]`.
+    Free {
+        name: &'static str,
+        required: bool,
+        help: &'static str,
+    },
+    /// A switch, e.g. `--release`.
+    Flag {
+        name: &'static str,
+        help: &'static str,
+    },
+    /// An option that takes a value, e.g. `--submit <part>`. Values named `path` complete to files.
+    Value {
+        name: &'static str,
+        value: &'static str,
+        help: &'static str,
+    },
+}
+
+impl Arg {
+    fn help(&self) -> &'static str {
+        match self {
+            Arg::Free { help, .. } | Arg::Flag { help, .. } | Arg::Value { help, .. } => help,
+        }
+    }
+
+    /// The argument as shown in help, e.g. `<day>`, `[<days>]` or `--submit <part>`.
+    fn label(&self) -> String {
+        match self {
+            Arg::Free {
+                name,
+                required: true,
+                ..
+            } => format!("<{name}>"),
+            Arg::Free { name, .. } => format!("[<{name}>]"),
+            Arg::Flag { name, .. } => (*name).to_string(),
+            Arg::Value { name, value, .. } => format!("{name} <{value}>"),
+        }
+    }
+}
+
+const DAY: Arg = Arg::Free {
+    name: "day",
+    required: true,
+    help: "Day of the puzzle, e.g. `1` or `01`",
+};
+
+const DAYS_HELP: &str = "Days, e.g. `7`, `1..5,7,12`, `all`, `solved` or `unsolved`";
+
+const DAYS: Arg = Arg::Free {
+    name: "days",
+    required: true,
+    help: DAYS_HELP,
+};
+
+const OPTIONAL_DAYS: Arg = Arg::Free {
+    name: "days",
+    required: false,
+    help: DAYS_HELP,
+};
+
+const TIMEOUT: Arg = Arg::Value {
+    name: "--timeout",
+    value: "duration",
+    help: "Cancel parts that run longer, e.g. `30s` or `none` (default: AOC_TIMEOUT)",
+};
+
+const RELEASE: Arg = Arg::Flag {
+    name: "--release",
+    help: "Build solutions with optimizations",
+};
+
+const FORCE: Arg = Arg::Flag {
+    name: "--force",
+    help: "Overwrite existing files",
+};
+
+/// Options that every command accepts.
+pub const GLOBAL_ARGS: &[Arg] = &[
+    Arg::Value {
+        name: "--year",
+        value: "year",
+        help: "Year of the puzzles (default: AOC_YEAR)",
+    },
+    Arg::Flag {
+        name: "--help",
+        help: "Print help",
+    },
+];
+
+pub const COMMANDS: &[Command] = &[
+    Command {
+        name: "scaffold",
+        about: "Create the solution, input and example files of a day",
+        args: &[
+            DAY,
+            Arg::Flag {
+                name: "--download",
+                help: "Download the input and puzzle description as well",
+            },
+            Arg::Flag {
+                name: "--overwrite",
+                help: "Overwrite an existing solution file",
+            },
+        ],
+    },
+    Command {
+        name: "download",
+        about: "Download the input and puzzle description of days",
+        args: &[DAYS, FORCE],
+    },
+    Command {
+        name: "read",
+        about: "Print the puzzle description of a day",
+        args: &[DAY],
+    },
+    Command {
+        name: "examples",
+        about: "Extract the example inputs from the puzzle description of a day",
+        args: &[
+            DAY,
+            Arg::Flag {
+                name: "--list",
+                help: "List the code blocks of the description",
+            },
+            Arg::Value {
+                name: "--part1",
+                value: "n",
+                help: "Use block `n` as example of part 1",
+            },
+            Arg::Value {
+                name: "--part2",
+                value: "n",
+                help: "Use block `n` as example of part 2",
+            },
+            FORCE,
+        ],
+    },
+    Command {
+        name: "solve",
+        about: "Run the solutions of days",
+        args: &[
+            DAYS,
+            RELEASE,
+            Arg::Flag {
+                name: "--dhat",
+                help: "Profile heap allocations with DHAT",
+            },
+            Arg::Value {
+                name: "--submit",
+                value: "part",
+                help: "Submit the answer of a part",
+            },
+            Arg::Value {
+                name: "--input",
+                value: "path",
+                help: "Run on a file, or on stdin with `-`",
+            },
+            Arg::Flag {
+                name: "--example",
+                help: "Run on the examples and compare with the expected answers",
+            },
+            Arg::Flag {
+                name: "--watch",
+                help: "Rerun whenever the solution, its input or examples change",
+            },
+            Arg::Flag {
+                name: "--test",
+                help: "Run the tests first in watch mode",
+            },
+        ],
+    },
+    Command {
+        name: "all",
+        about: "Run all solutions",
+        args: &[OPTIONAL_DAYS, RELEASE, TIMEOUT],
+    },
+    Command {
+        name: "time",
+        about: "Benchmark solutions",
+        args: &[
+            OPTIONAL_DAYS,
+            Arg::Flag {
+                name: "--all",
+                help: "Bench all days, not only the ones without stored timings",
+            },
+            Arg::Flag {
+                name: "--store",
+                help: "Store the timings and update the readme",
+            },
+            Arg::Flag {
+                name: "--memory",
+                help: "Collect heap stats in a DHAT build",
+            },
+            Arg::Flag {
+                name: "--compare",
+                help: "Compare with the stored timings",
+            },
+            Arg::Value {
+                name: "--threshold",
+                value: "percent",
+                help: "Slowdown that counts as a regression (default: 10)",
+            },
+            Arg::Value {
+                name: "--history",
+                value: "day",
+                help: "Print the benchmark history of a day",
+            },
+            TIMEOUT,
+        ],
+    },
+    Command {
+        name: "verify",
+        about: "Check answers against the accepted ones",
+        args: &[OPTIONAL_DAYS, TIMEOUT],
+    },
+    Command {
+        name: "submissions",
+        about: "Print the submitted answers of a day",
+        args: &[DAY],
+    },
+    Command {
+        name: "leaderboard",
+        about: "Print a private leaderboard",
+        args: &[
+            Arg::Free {
+                name: "id",
+                required: true,
+                help: "Id of the leaderboard",
+            },
+            Arg::Value {
+                name: "--file",
+                value: "path",
+                help: "Read the leaderboard from a JSON file",
+            },
+            Arg::Flag {
+                name: "--cached",
+                help: "Always use the cached leaderboard",
+            },
+            Arg::Value {
+                name: "--day",
+                value: "day",
+                help: "Print the solve times of a day",
+            },
+        ],
+    },
+    Command {
+        name: "stars",
+        about: "Update the stars table of the readme",
+        args: &[
+            Arg::Value {
+                name: "--file",
+                value: "path",
+                help: "Read the stars from a leaderboard JSON file",
+            },
+            Arg::Value {
+                name: "--user",
+                value: "id",
+                help: "Member of the leaderboard to read the stars of",
+            },
+        ],
+    },
+    #[cfg(feature = "today")]
+    Command {
+        name: "today",
+        about: "Scaffold, download and read the puzzle of today",
+        args: &[Arg::Flag {
+            name: "--wait",
+            help: "Wait for the next puzzle to unlock",
+        }],
+    },
+    Command {
+        name: "completions",
+        about: "Print a shell completion script",
+        args: &[Arg::Free {
+            name: "shell",
+            required: true,
+            help: "`bash`, `zsh` or `fish`",
+        }],
+    },
+    Command {
+        name: "help",
+        about: "Print help of a command",
+        args: &[Arg::Free {
+            name: "command",
+            required: false,
+            help: "Command to print help of",
+        }],
+    },
+];
+
+/// Looks up the spec of a command.
+pub fn command(name: &str) -> Option<&'static Command> {
+    COMMANDS.iter().find(|c| c.name == name)
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn usage(command: &Command) -> String {
+    let mut usage = format!("cargo {}", command.name);
+
+    for arg in command.args {
+        if let Arg::Free { .. } = arg {
+            usage.push(' ');
+            usage.push_str(&arg.label());
+        }
+    }
+
+    usage.push_str(" [options]");
+    usage
+}
+
+/// Short usage of a command, printed along with errors in its arguments.
+pub fn short_usage(command: &Command) -> String {
+    format!(
+        "Usage: {}\nRun `cargo {} --help` for more information.",
+        usage(command),
+        command.name
+    )
+}
+
+fn write_args(out: &mut String, heading: &str, args: &[&Arg]) {
+    if args.is_empty() {
+        return;
+    }
+
+    let width = args.iter().map(|x| x.label().len()).max().unwrap_or(0);
+
+    let _ = writeln!(out, "\n{ANSI_BOLD}{heading}:{ANSI_RESET}");
+    for arg in args {
+        let _ = writeln!(out, "  {:<width$}  {}", arg.label(), arg.help());
+    }
+}
+
+/// Help of a command: its usage, description, arguments and options.
+pub fn help(command: &Command) -> String {
+    let mut out = format!(
+        "{}\n\n{ANSI_BOLD}Usage:{ANSI_RESET} {}\n",
+        command.about,
+        usage(command)
+    );
+
+    let (free, options): (Vec<&Arg>, Vec<&Arg>) = command
+        .args
+        .iter()
+        .chain(GLOBAL_ARGS)
+        .partition(|x| matches!(x, Arg::Free { .. }));
+
+    write_args(&mut out, "Arguments", &free);
+    write_args(&mut out, "Options", &options);
+    out
+}
+
+/// Overview of all commands.
+pub fn overview() -> String {
+    let mut out = format!(
+        "Advent of Code template\n\n{ANSI_BOLD}Usage:{ANSI_RESET} cargo <command> [options]\n\n{ANSI_BOLD}Commands:{ANSI_RESET}\n"
+    );
+
+    let width = COMMANDS.iter().map(|c| c.name.len()).max().unwrap_or(0);
+
+    for command in COMMANDS {
+        let _ = writeln!(out, "  {:<width$}  {}", command.name, command.about);
+    }
+
+    let _ = write!(
+        out,
+        "\nRun `cargo <command> --help` for the arguments of a command."
+    );
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl FromStr for Shell {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bash" => Ok(Shell::Bash),
+            "zsh" => Ok(Shell::Zsh),
+            "fish" => Ok(Shell::Fish),
+            s => Err(format!(
+                "unknown shell `{s}`, expecting `bash`, `zsh` or `fish`"
+            )),
+        }
+    }
+}
+
+/// Options of a command, including the global ones.
+pub fn options(command: &Command) -> impl Iterator<Item = &Arg> {
+    command
+        .args
+        .iter()
+        .chain(GLOBAL_ARGS)
+        .filter(|x| !matches!(x, Arg::Free { .. }))
+}
+
+fn option_name(arg: &Arg) -> &'static str {
+    match arg {
+        Arg::Flag { name, .. } | Arg::Value { name, .. } | Arg::Free { name, .. } => name,
+    }
+}
+
+/// Returns the first option in `args` that the command does not accept, e.g. `--relase`.
+/// Values are accepted as `--name value` and `--name=value`.
+pub fn unknown_option<'a>(command: &Command, args: &'a [String]) -> Option<&'a str> {
+    args.iter()
+        .filter(|x| x.starts_with("--"))
+        .map(|x| x.split_once('=').map_or(x.as_str(), |(name, _)| name))
+        .find(|name| !options(command).any(|arg| option_name(arg) == *name))
+}
+
+/// Commands that are run as `cargo <command>`, i.e. have an alias in `.cargo/config.toml`.
+/// `help` is left out, as `cargo help` is a command of cargo itself.
+fn aliased_commands() -> impl Iterator<Item = &'static Command> {
+    COMMANDS.iter().filter(|c| c.name != "help")
+}
+
+/// Completion script for a shell. The scripts complete the commands of the template after `cargo`,
+/// and hand everything else to the completion of cargo itself.
+pub fn completions(shell: Shell) -> String {
+    match shell {
+        Shell::Bash => bash_completions(),
+        Shell::Zsh => zsh_completions(),
+        Shell::Fish => fish_completions(),
+    }
+}
+
+fn bash_completions() -> String {
+    let commands: Vec<&str> = aliased_commands().map(|c| c.name).collect();
+
+    let mut out = format!(
+        "# load the completion of cargo first, which is wrapped below.
+declare -F _cargo >/dev/null || _completion_loader cargo 2>/dev/null
+
+{COMPLETION_FN}() {{
+    local cur prev
+    cur=\"${{COMP_WORDS[COMP_CWORD]}}\"
+    prev=\"${{COMP_WORDS[COMP_CWORD-1]}}\"
+
+    if [[ $COMP_CWORD -eq 1 ]]; then
+        declare -F _cargo >/dev/null && _cargo \"$@\"
+        COMPREPLY+=($(compgen -W \"{}\" -- \"$cur\"))
+        return
+    fi
+
+    case \"${{COMP_WORDS[1]}}\" in
+        {})
+            ;;
+        *)
+            declare -F _cargo >/dev/null && _cargo \"$@\"
+            return
+            ;;
+    esac
+
+    case \"$prev\" in
+        --input|--file)
+            COMPREPLY=($(compgen -f -- \"$cur\"))
+            return
+            ;;
+    esac
+
+    case \"${{COMP_WORDS[1]}}\" in
+",
+        commands.join(" "),
+        commands.join("|")
+    );
+
+    for command in aliased_commands() {
+        let mut words: Vec<&str> = options(command).map(option_name).collect();
+
+        if command.name == "completions" {
+            words.extend(["bash", "zsh", "fish"]);
+        }
+
+        let _ = writeln!(
+            out,
+            "        {})\n            COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))\n            ;;",
+            command.name,
+            words.join(" ")
+        );
+    }
+
+    let _ = write!(
+        out,
+        "    esac\n}}\n\ncomplete -o default -F {COMPLETION_FN} cargo\n"
+    );
+    out
+}
+
+/// Escapes a description for the option specs of zsh's `_arguments` and `_describe`.
+fn zsh_escape(s: &str) -> String {
+    s.replace('\'', "'\\''")
+        .replace('[', "\\[")
+        .replace(']', "\\]")
+        .replace(':', "\\:")
+}
+
+fn zsh_completions() -> String {
+    let names: Vec<&str> = aliased_commands().map(|c| c.name).collect();
+
+    let mut out = format!("{COMPLETION_FN}() {{\n    local -a commands\n    commands=(\n");
+
+    for command in aliased_commands() {
+        let _ = writeln!(
+            out,
+            "        '{}:{}'",
+            command.name,
+            zsh_escape(command.about)
+        );
+    }
+
+    let _ = write!(
+        out,
+        "    )
+
+    if (( CURRENT == 2 )); then
+        _describe 'command' commands
+        (( $+functions[_cargo] )) && _cargo
+        return
+    fi
+
+    local command=$words[2]
+
+    case $command in
+        {})
+            ;;
+        *)
+            (( $+functions[_cargo] )) && _cargo
+            return
+            ;;
+    esac
+
+    shift words
+    (( CURRENT-- ))
+
+    case $command in
+",
+        names.join("|")
+    );
+
+    for command in aliased_commands() {
+        let _ = writeln!(out, "        {})\n            _arguments \\", command.name);
+
+        for arg in options(command) {
+            let spec = match arg {
+                Arg::Value {
+                    name, value, help, ..
+                } => {
+                    let action = if *value == "path" { "_files" } else { " " };
+                    format!("'{name}[{}]:{value}:{action}'", zsh_escape(help))
+                }
+                arg => format!("'{}[{}]'", option_name(arg), zsh_escape(arg.help())),
+            };
+            let _ = writeln!(out, "                {spec} \\");
+        }
+
+        let free = match command.name {
+            "completions" => "'1:shell:(bash zsh fish)'",
+            _ => "'*:: :'",
+        };
+        let _ = writeln!(out, "                {free}\n            ;;");
+    }
+
+    let _ = write!(out, "    esac\n}}\n\ncompdef {COMPLETION_FN} cargo\n");
+    out
+}
+
+fn fish_completions() -> String {
+    let mut out = String::new();
+
+    for command in aliased_commands() {
+        let _ = writeln!(
+            out,
+            "complete -c cargo -n __fish_use_subcommand -f -a {} -d '{}'",
+            command.name,
+            command.about.replace('\'', "\\'")
+        );
+    }
+
+    for command in aliased_commands() {
+        let condition = format!("'__fish_seen_subcommand_from {}'", command.name);
+
+        // only values named `path` complete to files.
+        let _ = writeln!(out, "complete -c cargo -n {condition} -f");
+
+        for arg in options(command) {
+            let name = option_name(arg).trim_start_matches("--");
+            let help = arg.help().replace('\'', "\\'");
+
+            let value = match arg {
+                Arg::Value { value: "path", .. } => " -r -F",
+                Arg::Value { .. } => " -x",
+                _ => "",
+            };
+
+            let _ = writeln!(
+                out,
+                "complete -c cargo -n {condition} -l {name}{value} -d '{help}'"
+            );
+        }
+
+        if command.name == "completions" {
+            let _ = writeln!(out, "complete -c cargo -n {condition} -a 'bash zsh fish'");
+        }
+    }
+
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{command, completions, help, short_usage, unknown_option, Arg, Shell, COMMANDS};
+
+    #[test]
+    fn has_consistent_spec() {
+        for command in COMMANDS {
+            for arg in command.args {
+                match arg {
+                    Arg::Free { name, .. } => assert!(!name.starts_with('-'), "{name}"),
+                    Arg::Flag { name, .. } | Arg::Value { name, .. } => {
+                        assert!(name.starts_with("--"), "{name}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn finds_unknown_options() {
+        let solve = command("solve").unwrap();
+        let args = |x: &[&str]| x.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            unknown_option(solve, &args(&["1", "--release", "--year=2023"])),
+            None
+        );
+        assert_eq!(
+            unknown_option(solve, &args(&["1", "--input", "-", "--relase"])),
+            Some("--relase")
+        );
+        assert_eq!(
+            unknown_option(command("read").unwrap(), &args(&["1", "--timeout=1s"])),
+            Some("--timeout")
+        );
+    }
+
+    #[test]
+    fn prints_help_of_commands() {
+        let solve = command("solve").unwrap();
+        let help = help(solve);
+
+        assert!(help.contains("cargo solve <days> [options]"));
+        assert!(help.contains("--submit <part>"));
+        assert!(help.contains("--year <year>"));
+
+        assert_eq!(
+            short_usage(command("verify").unwrap()).lines().next(),
+            Some("Usage: cargo verify [<days>] [options]")
+        );
+        assert!(command("unknown").is_none());
+    }
+
+    #[test]
+    fn completes_every_command() {
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish] {
+            let script = completions(shell);
+            for command in COMMANDS.iter().filter(|c| c.name != "help") {
+                assert!(script.contains(command.name), "{shell:?} {}", command.name);
+            }
+            assert!(script.contains("year"));
+            assert!(script.contains("cargo"));
+        }
+    }
+
+    #[test]
+    fn parses_shells() {
+        assert_eq!("zsh".parse::<Shell>(), Ok(Shell::Zsh));
+        assert!("powershell".parse::<Shell>().is_err());
+    }
+}
@@ -1,5 +1,48 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::{path::Path, time::Duration};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::commands::CommandError;
+use crate::template::inputs::{self, InputStatus};
+use crate::template::{run_multi::run_multi, Day, DaySet, Year, ANSI_RESET, ANSI_YELLOW};
+
+/// Run the given days, or all days, and print their answers.
+pub fn handle(
+    year: Year,
+    days: Option<DaySet>,
+    is_release: bool,
+    timeout: Option<Duration>,
+) -> Result<(), CommandError> {
+    let days = days.unwrap_or_else(DaySet::all).resolve(year);
+
+    // days without a solution are skipped by `run_multi`, so their inputs do not matter.
+    for day in days
+        .iter()
+        .filter(|day| Path::new(&year.bin_path(**day)).exists())
+    {
+        warn_about_input(year, *day);
+    }
+
+    run_multi(
+        year,
+        &days.into_iter().collect(),
+        is_release,
+        false,
+        timeout,
+    );
+
+    Ok(())
+}
+
+/// Warn about inputs that are empty or differ from the downloaded ones, which usually makes solutions fail.
+fn warn_about_input(year: Year, day: Day) {
+    let problem = match inputs::check(year, day) {
+        InputStatus::Ok | InputStatus::Untracked => return,
+        InputStatus::Missing => "is missing",
+        InputStatus::Empty => "is empty",
+        InputStatus::Modified => "was changed since it was downloaded",
+    };
+
+    eprintln!(
+        "{ANSI_YELLOW}Warning:{ANSI_RESET} input \"{}\" {problem}.",
+        year.data_path("inputs", day)
+    );
 }
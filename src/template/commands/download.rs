@@ -1,14 +1,76 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use crate::template::commands::CommandError;
+use crate::template::{aoc_cli, history, inputs, Day, Year};
+use std::{thread, time::Duration};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+/// Delay before the first retry of `handle_with_backoff`, doubled after every failed attempt.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(5);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+const MAX_ATTEMPTS: u32 = 8;
+
+pub fn handle(year: Year, day: Day, force: bool) -> Result<(), CommandError> {
+    if !force && skip_existing(year, day) {
+        return Ok(());
+    }
+
+    prepare(year)?;
+    aoc_cli::download(year, day).map_err(CommandError::aoc(format!("download day {day}")))?;
+    record_checksum(year, day);
+    Ok(())
+}
+
+/// Download the input of a day that just unlocked. The input may not be available right at the unlock,
+/// so failed downloads are retried with increasing delays.
+pub fn handle_with_backoff(year: Year, day: Day) -> Result<(), CommandError> {
+    if skip_existing(year, day) {
+        return Ok(());
+    }
+
+    prepare(year)?;
+
+    let mut delay = INITIAL_BACKOFF;
+    let mut attempt = 1;
+
+    loop {
+        match aoc_cli::download(year, day) {
+            Ok(()) => {
+                record_checksum(year, day);
+                return Ok(());
+            }
+            Err(e) if attempt < MAX_ATTEMPTS && e.is_retryable() => {
+                eprintln!(
+                    "Download attempt {attempt} failed: {e} Retrying in {}s...",
+                    delay.as_secs()
+                );
+                thread::sleep(delay);
+                delay = (delay * 2).min(MAX_BACKOFF);
+                attempt += 1;
+            }
+            Err(e) => return Err(CommandError::aoc(format!("download day {day}"))(e)),
+        }
     }
+}
+
+fn skip_existing(year: Year, day: Day) -> bool {
+    let exists = inputs::exists(year, day);
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+    if exists {
+        println!(
+            "Input \"{}\" already exists, skipping download. Pass `--force` to download it again.",
+            year.data_path("inputs", day)
+        );
+    }
+
+    exists
+}
+
+fn prepare(year: Year) -> Result<(), CommandError> {
+    aoc_cli::check(year)?;
+    year.create_data_dirs()
+        .map_err(CommandError::io("create data directories"))
+}
+
+fn record_checksum(year: Year, day: Day) {
+    if let Err(e) = inputs::record(year, day, history::unix_now()) {
+        eprintln!("Failed to record input checksum: {e}");
+    }
 }
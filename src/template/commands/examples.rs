@@ -0,0 +1,110 @@
+use std::fs;
+
+use crate::template::commands::scaffold::fill_expected_answers;
+use crate::template::commands::CommandError;
+use crate::template::examples::{code_blocks, example_paths, pick, CodeBlock};
+use crate::template::{Day, Year, ANSI_BOLD, ANSI_RESET};
+
+/// Number of lines of a code block that `--list` prints.
+const PREVIEW_LINES: usize = 5;
+
+/// Writes the example inputs found in the puzzle description of a day to the example files,
+/// and fills expected example answers into tests that do not have one yet.
+/// `picks` holds the 1-based block numbers to use for each part, as printed by `--list`.
+pub fn handle(
+    year: Year,
+    day: Day,
+    list: bool,
+    picks: [Option<usize>; 2],
+    force: bool,
+) -> Result<(), CommandError> {
+    let puzzle_path = year.puzzle_path(day);
+
+    let Ok(puzzle) = fs::read_to_string(&puzzle_path) else {
+        return Err(CommandError::Data(format!(
+            "Puzzle description \"{puzzle_path}\" not found. Run `cargo download {day} --year {year}` first."
+        )));
+    };
+
+    let blocks = code_blocks(&puzzle);
+
+    if blocks.is_empty() {
+        return Err(CommandError::Data(format!(
+            "No code blocks found in \"{puzzle_path}\"."
+        )));
+    }
+
+    if list {
+        print_blocks(&blocks);
+        return Ok(());
+    }
+
+    let mut selected = [None, None];
+
+    for (part, pick_arg) in [1, 2].into_iter().zip(picks) {
+        selected[usize::from(part - 1)] = match pick_arg {
+            Some(n) if (1..=blocks.len()).contains(&n) => Some(n - 1),
+            Some(n) => {
+                return Err(CommandError::Usage(format!(
+                    "There is no code block {n}, the puzzle has {} blocks. Use `--list` to show them.",
+                    blocks.len()
+                )));
+            }
+            None => pick(&blocks, part),
+        };
+    }
+
+    let [Some(part_1), part_2] = selected else {
+        return Err(CommandError::Usage(
+            "Could not pick an example for part 1. Use `--part1 <n>` to choose one.".into(),
+        ));
+    };
+
+    let part_2 = part_2.unwrap_or(part_1);
+    let is_shared = blocks[part_1].content == blocks[part_2].content;
+
+    for (part, path) in example_paths(year, day, is_shared) {
+        let index = if part == 1 { part_1 } else { part_2 };
+
+        let is_empty = fs::read_to_string(&path).map_or(true, |s| s.trim().is_empty());
+
+        if !is_empty && !force {
+            println!("Kept existing example file \"{path}\", pass `--force` to overwrite it.");
+            continue;
+        }
+
+        fs::write(&path, &blocks[index].content).map_err(CommandError::io("write example file"))?;
+        println!(
+            "Wrote block {} as example of part {part} to \"{path}\"",
+            index + 1
+        );
+    }
+
+    fill_expected_answers(year, day)?;
+
+    if !is_shared {
+        println!("---");
+        println!("🎄 Part 2 has its own example, use `read_file_part(\"examples\", DAY, 2)` in its test.");
+    }
+
+    Ok(())
+}
+
+fn print_blocks(blocks: &[CodeBlock]) {
+    for (i, block) in blocks.iter().enumerate() {
+        println!(
+            "{ANSI_BOLD}Block {}{ANSI_RESET} (part {}, {} lines)",
+            i + 1,
+            block.part,
+            block.line_count()
+        );
+
+        for line in block.content.lines().take(PREVIEW_LINES) {
+            println!("    {line}");
+        }
+
+        if block.line_count() > PREVIEW_LINES {
+            println!("    ...");
+        }
+    }
+}
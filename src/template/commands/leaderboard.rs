@@ -0,0 +1,185 @@
+use std::fs;
+
+use crate::template::aoc_client::Client;
+use crate::template::commands::CommandError;
+use crate::template::history::format_timestamp;
+use crate::template::leaderboard::{format_elapsed, Leaderboard, Member};
+use crate::template::request_gate::LEADERBOARD_INTERVAL;
+use crate::template::{all_days, Day, Year, ANSI_BOLD, ANSI_RESET, ANSI_YELLOW};
+
+/// Print a private leaderboard, ranked by local score, and who solved part 2 of each day the fastest.
+/// The leaderboard is read from `file` if given, otherwise it is fetched unless the cached copy is recent enough.
+/// With `day`, only the solve times of that day are printed.
+pub fn handle(
+    year: Year,
+    id: u64,
+    file: Option<String>,
+    cached: bool,
+    day: Option<Day>,
+) -> Result<(), CommandError> {
+    let json = match file {
+        Some(path) => read(&path)?,
+        None => fetch_or_cached(year, id, cached)?,
+    };
+
+    let leaderboard: Leaderboard = json
+        .parse()
+        .map_err(|e| CommandError::Data(format!("Failed to read leaderboard: {e}")))?;
+
+    match day {
+        Some(day) => print_day(&leaderboard, year, day),
+        None => {
+            print_ranking(&leaderboard, year, id);
+            print_fastest(&leaderboard, year);
+        }
+    }
+
+    Ok(())
+}
+
+fn cache_path(year: Year, id: u64) -> String {
+    format!("{}/leaderboard-{id}.json", year.data_dir())
+}
+
+fn read(path: &str) -> Result<String, CommandError> {
+    fs::read_to_string(path).map_err(CommandError::io(format!("read \"{path}\"")))
+}
+
+/// Returns the cached leaderboard if it is recent enough, or `cached` is set, and fetches it otherwise.
+/// A stale cache is still used when the fetch fails.
+fn fetch_or_cached(year: Year, id: u64, cached: bool) -> Result<String, CommandError> {
+    let path = cache_path(year, id);
+
+    let age = fs::metadata(&path)
+        .and_then(|m| m.modified())
+        .ok()
+        .map(|modified| modified.elapsed().unwrap_or_default());
+
+    if let Some(age) = age {
+        // fetching earlier would be refused by the request gate anyway.
+        if cached || age < LEADERBOARD_INTERVAL {
+            println!(
+                "Using cached leaderboard \"{path}\" from {}m ago.\n",
+                age.as_secs() / 60
+            );
+            return read(&path);
+        }
+    } else if cached {
+        return Err(CommandError::Data(format!(
+            "No cached leaderboard at \"{path}\"."
+        )));
+    }
+
+    let fetched = Client::from_env(year).and_then(|mut client| client.leaderboard(id));
+
+    match (fetched, age) {
+        (Ok(json), _) => {
+            if let Err(e) = year
+                .create_data_dirs()
+                .and_then(|()| fs::write(&path, &json))
+            {
+                eprintln!("Failed to cache leaderboard: {e}");
+            }
+            Ok(json)
+        }
+        (Err(e), Some(_)) => {
+            eprintln!("Failed to fetch leaderboard, using the cached one: {e}\n");
+            read(&path)
+        }
+        (Err(e), None) => Err(CommandError::aoc("fetch leaderboard")(e.into())),
+    }
+}
+
+/// Stars of a day, `*` if both parts are solved and `+` if only part 1 is.
+fn stars(member: &Member, day: Day) -> String {
+    match (member.star_ts(day, 1), member.star_ts(day, 2)) {
+        (_, Some(_)) => format!("{ANSI_YELLOW}*{ANSI_RESET}"),
+        (Some(_), None) => "+".into(),
+        (None, None) => ".".into(),
+    }
+}
+
+fn print_ranking(leaderboard: &Leaderboard, year: Year, id: u64) {
+    println!("{ANSI_BOLD}Private leaderboard {id} ({year}){ANSI_RESET}");
+    println!();
+
+    let tens: String = all_days().map(|d| d.to_string().remove(0)).collect();
+    let ones: String = all_days().map(|d| d.to_string().remove(1)).collect();
+    println!("{:<4}  {:>5}  {tens}", "", "");
+    println!(
+        "{:<4}  {:>5}  {ones}  {:>5}  {:<16}  Name",
+        "Rank", "Score", "Stars", "Last star"
+    );
+
+    for (rank, member) in leaderboard.ranked().into_iter().enumerate() {
+        let days: String = all_days().map(|day| stars(member, day)).collect();
+        let last_star = if member.last_star_ts > 0 {
+            format_timestamp(member.last_star_ts)
+        } else {
+            "-".into()
+        };
+
+        println!(
+            "{:<4}  {:>5}  {days}  {:>5}  {last_star:<16}  {}",
+            format!("{})", rank + 1),
+            member.local_score,
+            member.stars,
+            member.display_name()
+        );
+    }
+
+    println!();
+    println!("`*` both parts solved, `+` part 1 solved. Times are UTC.");
+}
+
+fn print_fastest(leaderboard: &Leaderboard, year: Year) {
+    println!();
+    println!("{ANSI_BOLD}Fastest part 2{ANSI_RESET}");
+
+    let mut any = false;
+
+    for day in all_days() {
+        if let Some((member, elapsed)) = leaderboard.part_two_times(year, day).first() {
+            println!(
+                "Day {day}: {} in {}",
+                member.display_name(),
+                format_elapsed(*elapsed)
+            );
+            any = true;
+        }
+    }
+
+    if !any {
+        println!("Nobody solved a part 2 yet.");
+    }
+}
+
+/// Print everyone who solved part 2 of a day, fastest first, with the time they took for each part.
+fn print_day(leaderboard: &Leaderboard, year: Year, day: Day) {
+    println!("{ANSI_BOLD}Day {day} ({year}){ANSI_RESET}");
+    println!("------");
+
+    let times = leaderboard.part_two_times(year, day);
+
+    if times.is_empty() {
+        println!("Nobody solved part 2 yet.");
+        return;
+    }
+
+    let unlock = year.unlock_timestamp(day);
+
+    println!("{:<4}  {:<11}  {:<11}  Name", "Rank", "Part 1", "Part 2");
+
+    for (rank, (member, elapsed)) in times.into_iter().enumerate() {
+        let part_1 = member
+            .star_ts(day, 1)
+            .map_or("-".into(), |ts| format_elapsed(ts.saturating_sub(unlock)));
+
+        println!(
+            "{:<4}  {part_1:<11}  {:<11}  {}",
+            format!("{})", rank + 1),
+            format_elapsed(elapsed),
+            member.display_name()
+        );
+    }
+}
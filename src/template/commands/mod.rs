@@ -1,6 +1,87 @@
 pub mod all;
 pub mod download;
+pub mod examples;
+pub mod leaderboard;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+pub mod stars;
+pub mod submissions;
 pub mod time;
+#[cfg(feature = "today")]
+pub mod today;
+pub mod verify;
+
+use std::{fmt::Display, io};
+
+use crate::template::aoc_cli::AocCommandError;
+
+/// Error of a command. `main` prints it and exits with [`CommandError::exit_code`].
+#[derive(Debug)]
+pub enum CommandError {
+    /// The arguments are invalid or can not be combined.
+    Usage(String),
+    /// Access to Advent of Code is not set up, or a request to it failed.
+    Aoc {
+        action: String,
+        source: AocCommandError,
+    },
+    /// Reading or writing a file failed.
+    Io { action: String, source: io::Error },
+    /// A file the command needs is missing or can not be read, e.g. a puzzle description.
+    Data(String),
+    /// A check failed, e.g. an answer did not match. The details were printed before.
+    Check(String),
+    /// A solution binary exited with a non-zero status, which is passed on.
+    Solution(i32),
+}
+
+impl CommandError {
+    pub fn io(action: impl Into<String>) -> impl FnOnce(io::Error) -> Self {
+        let action = action.into();
+        move |source| CommandError::Io { action, source }
+    }
+
+    pub fn aoc(action: impl Into<String>) -> impl FnOnce(AocCommandError) -> Self {
+        let action = action.into();
+        move |source| CommandError::Aoc { action, source }
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CommandError::Solution(code) => *code,
+            _ => 1,
+        }
+    }
+}
+
+impl Display for CommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CommandError::Usage(message)
+            | CommandError::Data(message)
+            | CommandError::Check(message) => write!(f, "{message}"),
+            CommandError::Aoc { action, source } if action.is_empty() => write!(f, "{source}"),
+            CommandError::Aoc { action, source } => write!(f, "Failed to {action}: {source}"),
+            CommandError::Io { action, source } => write!(f, "Failed to {action}: {source}"),
+            CommandError::Solution(code) => write!(f, "The solution exited with status {code}."),
+        }
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+impl From<AocCommandError> for CommandError {
+    fn from(source: AocCommandError) -> Self {
+        CommandError::Aoc {
+            action: String::new(),
+            source,
+        }
+    }
+}
+
+impl From<pico_args::Error> for CommandError {
+    fn from(e: pico_args::Error) -> Self {
+        CommandError::Usage(e.to_string())
+    }
+}
@@ -1,14 +1,17 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
-    process,
+    fs::{self, File, OpenOptions},
+    io::{ErrorKind, Write},
 };
 
-use crate::template::Day;
+use crate::template::commands::CommandError;
+use crate::template::examples::{expected_answers, fill_expected_answer};
+use crate::template::{Day, Year};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
+const SOLUTIONS_PATH: &str = "src/solutions.rs";
+
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
     if overwrite {
@@ -19,61 +22,118 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
-}
+/// Adds the solution to the registry of the main binary. Returns `false` if it is already registered.
+fn register_solution(year: Year, day: Day) -> Result<bool, std::io::Error> {
+    let mut solutions = fs::read_to_string(SOLUTIONS_PATH)?;
+    let bin_name = year.bin_name(day);
+
+    if solutions.contains(&format!("\"bin/{bin_name}.rs\"")) {
+        return Ok(false);
+    }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+    let pos = solutions.rfind('}').ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "could not find end of solution registry",
+        )
+    })?;
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
-        }
+    // NOTE: module names can not start with a digit, so the names of year-scoped binaries get a prefix.
+    let module = if year.is_legacy() {
+        format!("day_{day}")
+    } else {
+        format!("year_{bin_name}")
     };
 
-    match file.write_all(
+    solutions.insert_str(pos, &format!("    {module} => \"bin/{bin_name}.rs\",\n"));
+    fs::write(SOLUTIONS_PATH, solutions)?;
+    Ok(true)
+}
+
+/// Creates an empty data file, keeping an existing one so that inputs and examples are never lost.
+fn create_data_file(path: &str, kind: &str) -> Result<(), CommandError> {
+    match safe_create_file(path, false) {
+        Ok(_) => {
+            println!("Created empty {kind} file \"{path}\"");
+        }
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+            println!("Kept existing {kind} file \"{path}\"");
+        }
+        Err(e) => return Err(CommandError::io(format!("create {kind} file"))(e)),
+    }
+
+    Ok(())
+}
+
+pub fn handle(year: Year, day: Day, overwrite: bool) -> Result<(), CommandError> {
+    let input_path = year.data_path("inputs", day);
+    let example_path = year.data_path("examples", day);
+    let module_path = year.bin_path(day);
+
+    year.create_data_dirs()
+        .map_err(CommandError::io("create data directories"))?;
+
+    let mut file = safe_create_file(&module_path, overwrite)
+        .map_err(CommandError::io("create module file"))?;
+
+    file.write_all(
         MODULE_TEMPLATE
             .replace("%DAY_NUMBER%", &day.into_inner().to_string())
             .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
-        }
+    )
+    .map_err(CommandError::io("write module contents"))?;
+    println!("Created module file \"{}\"", &module_path);
+
+    if register_solution(year, day).map_err(CommandError::io("register solution"))? {
+        println!("Registered solution in \"{SOLUTIONS_PATH}\"");
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
-        }
+    create_data_file(&input_path, "input")?;
+    create_data_file(&example_path, "example")?;
+
+    fill_expected_answers(year, day)?;
+
+    println!("---");
+    if year.is_legacy() {
+        println!("🎄 Type `cargo solve {day}` to run your solution.");
+    } else {
+        println!("🎄 Type `cargo solve {day} --year {year}` to run your solution.");
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+    Ok(())
+}
+
+/// Fills the expected example answers from the downloaded puzzle description into the tests of a solution.
+/// Only tests that still assert `None` are changed, so this can be run again once part 2 is unlocked.
+pub fn fill_expected_answers(year: Year, day: Day) -> Result<(), CommandError> {
+    let module_path = year.bin_path(day);
+
+    let Ok(puzzle) = fs::read_to_string(year.puzzle_path(day)) else {
+        return Ok(());
+    };
+
+    let Ok(mut module) = fs::read_to_string(&module_path) else {
+        return Ok(());
+    };
+
+    let mut filled = false;
+
+    for (part, answer) in [1, 2].into_iter().zip(expected_answers(&puzzle)) {
+        // NOTE: the template returns numbers, so other answers are left for the user to fill in.
+        let Some(answer) = answer.filter(|x| x.parse::<i64>().is_ok()) else {
+            continue;
+        };
+
+        if let Some(source) = fill_expected_answer(&module, part, &answer) {
+            println!("Filled expected example answer {answer} into the test of part {part}");
+            module = source;
+            filled = true;
         }
     }
 
-    println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    if filled {
+        fs::write(&module_path, module).map_err(CommandError::io("write module file"))?;
+    }
+
+    Ok(())
 }
@@ -1,9 +1,84 @@
 use std::process::{Command, Stdio};
+use std::{thread, time::Duration};
 
-use crate::template::Day;
+use crate::template::commands::CommandError;
+use crate::template::protocol::PartReport;
+use crate::template::run_multi::child_commands;
+use crate::template::watch::{diff_answers, watched_paths, AnswerChange, Snapshot};
+use crate::template::{
+    Day, DaySet, Year, ANSI_BOLD, ANSI_GREEN, ANSI_ITALIC, ANSI_RED, ANSI_RESET, ANSI_YELLOW,
+};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// Interval at which watched files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Run the solutions of the given days via `cargo run`, one after the other.
+/// `input` is forwarded to the solution binary, to run it on a file or, with `-`, on stdin instead of the puzzle input.
+/// With `example`, the solutions run on their example inputs and their answers are checked against the expected ones.
+/// Fails with the status of the last day that failed, after all days ran.
+pub fn handle(
+    year: Year,
+    days: &DaySet,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    input: Option<&str>,
+    example: bool,
+) -> Result<(), CommandError> {
+    let days = days.resolve(year);
+
+    let usage = |message: &str| Err(CommandError::Usage(message.into()));
+
+    if days.is_empty() {
+        return usage("No days to solve.");
+    }
+
+    if submit_part.is_some() && days.len() > 1 {
+        return usage("`--submit` only works with a single day.");
+    }
+
+    if input.is_some() && days.len() > 1 {
+        return usage("`--input` only works with a single day.");
+    }
+
+    if input.is_some() && submit_part.is_some() {
+        return usage("`--submit` only works with the puzzle input, not with `--input`.");
+    }
+
+    if example && (input.is_some() || submit_part.is_some()) {
+        return usage("`--example` can not be combined with `--input` or `--submit`.");
+    }
+
+    let mut result = Ok(());
+
+    for (i, day) in days.iter().enumerate() {
+        if days.len() > 1 {
+            if i > 0 {
+                println!();
+            }
+            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+            println!("------");
+        }
+
+        if let Err(e) = run(year, *day, release, dhat, submit_part, input, example) {
+            result = Err(e);
+        }
+    }
+
+    result
+}
+
+/// Run the solution of a day via `cargo run`.
+fn run(
+    year: Year,
+    day: Day,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    input: Option<&str>,
+    example: bool,
+) -> Result<(), CommandError> {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), year.bin_name(day)];
 
     if dhat {
         cmd_args.extend([
@@ -23,12 +98,126 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
-    let mut cmd = Command::new("cargo")
+    if let Some(input) = input {
+        cmd_args.push("--input".to_string());
+        cmd_args.push(input.to_string());
+    }
+
+    if example {
+        cmd_args.push("--example".to_string());
+    }
+
+    let status = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
+        .status()
+        .map_err(CommandError::io("run cargo"))?;
 
-    cmd.wait().unwrap();
+    // forward the exit status, e.g. of a rejected submission.
+    if status.success() {
+        Ok(())
+    } else {
+        Err(CommandError::Solution(status.code().unwrap_or(1)))
+    }
+}
+
+/// Rebuild and rerun the solution of a day whenever its source, `src/lib.rs`, its input or its examples change,
+/// and show how the answers changed since the last run. With `test`, the tests of the day run first
+/// and the solution only runs if they pass. Runs until interrupted.
+pub fn watch(year: Year, day: Day, release: bool, test: bool) -> Result<(), CommandError> {
+    let paths = watched_paths(year, day);
+    let mut snapshot = Snapshot::take(&paths);
+    let mut previous: Option<Vec<PartReport>> = None;
+
+    loop {
+        if let Some(reports) = run_watched(year, day, release, test) {
+            if let Some(previous) = &previous {
+                print_answer_diff(previous, &reports);
+            }
+            previous = Some(reports);
+        }
+
+        println!("\n{ANSI_ITALIC}Watching for changes, press Ctrl+C to stop.{ANSI_RESET}");
+
+        let changed = loop {
+            thread::sleep(POLL_INTERVAL);
+
+            let next = Snapshot::take(&paths);
+            let changed: Vec<String> = next
+                .changed_since(&snapshot)
+                .into_iter()
+                .map(String::from)
+                .collect();
+            snapshot = next;
+
+            if !changed.is_empty() {
+                break changed;
+            }
+        };
+
+        // NOTE: editors may write a file in several steps, let them settle before building.
+        thread::sleep(POLL_INTERVAL);
+        snapshot = Snapshot::take(&paths);
+
+        println!();
+        println!("{ANSI_BOLD}Changed: {}{ANSI_RESET}", changed.join(", "));
+        println!("------");
+    }
+}
+
+/// Run the tests, if asked to, and the solution. Returns `None` if either could not run to completion.
+fn run_watched(year: Year, day: Day, release: bool, test: bool) -> Option<Vec<PartReport>> {
+    if test {
+        let passed = Command::new("cargo")
+            .args(["test", "--quiet", "--bin", &year.bin_name(day)])
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status()
+            .is_ok_and(|status| status.success());
+
+        if !passed {
+            println!("{ANSI_RED}Tests failed{ANSI_RESET}, not running the solution.");
+            return None;
+        }
+    }
+
+    match child_commands::rebuild_and_run(year, day, release) {
+        Ok(Some(reports)) => {
+            if reports.is_empty() {
+                println!("Not solved.");
+            }
+            Some(reports)
+        }
+        Ok(None) => {
+            println!("{ANSI_RED}Build failed.{ANSI_RESET}");
+            None
+        }
+        Err(e) => {
+            eprintln!("Failed to run day {day}: {e:?}");
+            None
+        }
+    }
+}
+
+fn print_answer_diff(previous: &[PartReport], reports: &[PartReport]) {
+    println!("\n{ANSI_BOLD}Answers since the last run{ANSI_RESET}");
+
+    let answer = |x: &Option<String>| x.clone().unwrap_or_else(|| "✖".into());
+
+    for (part, change) in diff_answers(previous, reports) {
+        match change {
+            AnswerChange::Unchanged(x) => {
+                println!("Part {part}: {} (unchanged)", answer(&x));
+            }
+            AnswerChange::Changed { before, after } => println!(
+                "Part {part}: {ANSI_RED}{}{ANSI_RESET} -> {ANSI_GREEN}{}{ANSI_RESET}",
+                answer(&before),
+                answer(&after)
+            ),
+            AnswerChange::New(x) => {
+                println!("Part {part}: {ANSI_YELLOW}{}{ANSI_RESET} (new)", answer(&x));
+            }
+        }
+    }
 }
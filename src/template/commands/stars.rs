@@ -0,0 +1,64 @@
+use std::fs;
+
+use crate::template::commands::CommandError;
+use crate::template::leaderboard::Leaderboard;
+use crate::template::readme_stars::{self, Progress};
+use crate::template::Year;
+
+/// Rebuild the stars table of the readme. Stars are read from the progress of a member in a
+/// private leaderboard JSON if `file` is given, otherwise from the local answer and submission records.
+/// The local records miss days solved elsewhere, so their stars are added to the ones already in the table.
+pub fn handle(year: Year, file: Option<String>, user: Option<u64>) -> Result<(), CommandError> {
+    let missing_marker = |_| {
+        CommandError::Data("Failed to update the stars table. Is the `advent_readme_stars` marker present in the readme?".into())
+    };
+
+    let progress = match file {
+        Some(path) => read_member_progress(&path, user)?,
+        None => {
+            let records = Progress::from_records(year);
+
+            if records.count() == 0 {
+                println!("No stars of {year} in the local answer and submission records, keeping the stars table. Pass `--file <leaderboard.json>` to read stars from a private leaderboard.");
+                return Ok(());
+            }
+
+            let mut progress = readme_stars::read_stars(year).map_err(missing_marker)?;
+            progress.merge(&records);
+            progress
+        }
+    };
+
+    readme_stars::update(year, &progress).map_err(missing_marker)?;
+
+    println!(
+        "Updated the stars table of {year} with {} stars.",
+        progress.count()
+    );
+    Ok(())
+}
+
+/// Reads the progress of a member from a leaderboard JSON. The member can be left out if it is the only one.
+fn read_member_progress(path: &str, user: Option<u64>) -> Result<Progress, CommandError> {
+    let leaderboard = fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|s| s.parse::<Leaderboard>())
+        .map_err(|e| CommandError::Data(format!("Failed to read leaderboard \"{path}\": {e}")))?;
+
+    let member = match user {
+        Some(id) => leaderboard.members.iter().find(|m| m.id == id),
+        None if leaderboard.members.len() == 1 => leaderboard.members.first(),
+        None => {
+            return Err(CommandError::Usage(
+                "The leaderboard has several members, pass `--user <id>` to pick one.".into(),
+            ));
+        }
+    };
+
+    member.map(Progress::from_member).ok_or_else(|| {
+        CommandError::Usage(format!(
+            "User {} is not a member of the leaderboard.",
+            user.unwrap_or_default()
+        ))
+    })
+}
@@ -0,0 +1,52 @@
+use crate::template::commands::CommandError;
+use crate::template::history::format_timestamp;
+use crate::template::submissions::{SubmissionVerdict, Submissions};
+use crate::template::{Day, Year, ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_RESET};
+
+/// Print every answer submitted for a day, along with the bounds known from `too high` / `too low` hints.
+pub fn handle(year: Year, day: Day) -> Result<(), CommandError> {
+    let submissions = Submissions::read_from_file(year);
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+
+    for part in [1, 2] {
+        let entries: Vec<_> = submissions.for_part(day, part).collect();
+
+        println!("\n{ANSI_BOLD}Part {part}{ANSI_RESET}");
+
+        if entries.is_empty() {
+            println!("No submissions.");
+            continue;
+        }
+
+        println!("{:<16}  {:<20}  Verdict", "Date", "Answer");
+
+        for submission in entries {
+            let verdict = match submission.verdict {
+                SubmissionVerdict::Correct => format!("{ANSI_GREEN}correct{ANSI_RESET}"),
+                SubmissionVerdict::Incorrect { hint: Some(hint) } => {
+                    format!("{ANSI_RED}incorrect{ANSI_RESET} ({hint})")
+                }
+                verdict => format!("{ANSI_RED}{verdict}{ANSI_RESET}"),
+            };
+
+            println!(
+                "{:<16}  {:<20}  {verdict}",
+                format_timestamp(submission.timestamp),
+                submission.answer
+            );
+        }
+
+        match submissions.bounds(day, part) {
+            (None, None) => {}
+            (too_low, too_high) => println!(
+                "Known bounds: {} < answer < {}",
+                too_low.map_or("?", |s| s.answer.as_str()),
+                too_high.map_or("?", |s| s.answer.as_str())
+            ),
+        }
+    }
+
+    Ok(())
+}
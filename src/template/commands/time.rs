@@ -1,31 +1,61 @@
 use std::collections::HashSet;
+use std::time::Duration;
 
-use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::commands::CommandError;
+use crate::template::history::{self, format_timestamp, HistoryRecord};
+use crate::template::run_multi::{run_memory, run_multi};
+use crate::template::stats::{format_nanos, BenchStats};
+use crate::template::timings::{percent_change, Timing, Timings};
+use crate::template::{
+    all_days, readme_benchmarks, Day, DaySet, Year, ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_RESET,
+};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+pub fn handle(
+    year: Year,
+    days: Option<DaySet>,
+    run_all: bool,
+    store: bool,
+    compare: Option<f64>,
+    memory: bool,
+    timeout: Option<Duration>,
+) -> Result<(), CommandError> {
     let stored_timings = Timings::read_from_file();
 
-    let days_to_run = day.map_or_else(
+    let days_to_run: HashSet<Day> = days.map_or_else(
         || {
             if run_all {
                 all_days().collect()
+            } else if compare.is_some() {
+                // when comparing, bench the days we have stored timings for.
+                stored_timings.days(year).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
                 all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
+                    .filter(|day| !stored_timings.is_day_complete(year, *day))
                     .collect()
             }
         },
-        |day| HashSet::from([day]),
+        |days| days.resolve(year).into_iter().collect(),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let mut timings = run_multi(year, &days_to_run, true, true, timeout).unwrap_or_default();
+
+    if memory {
+        timings = timings.with_memory(&run_memory(year, &days_to_run, timeout));
+    }
+
+    let has_regressions =
+        compare.is_some_and(|threshold| print_comparison(&stored_timings, &timings, threshold));
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings
+            .store_file()
+            .map_err(CommandError::io("store timings"))?;
+
+        if let Err(e) = history::append(&HistoryRecord::capture(timings)) {
+            eprintln!("Failed to append to benchmark history: {e}");
+        }
 
         println!();
         match readme_benchmarks::update(merged_timings) {
@@ -37,4 +67,148 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    if has_regressions {
+        return Err(CommandError::Check(
+            "Some steps got slower than the threshold.".into(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Print the change of every benched step against the stored timings.
+/// Returns `true` if any step got slower by more than `threshold` percent.
+fn print_comparison(stored_timings: &Timings, timings: &Timings, threshold: f64) -> bool {
+    let comparisons = stored_timings.compare(timings);
+
+    println!("\n{ANSI_BOLD}Comparison (threshold: {threshold}%){ANSI_RESET}");
+
+    if comparisons.is_empty() {
+        println!("No stored timings to compare against.");
+        return false;
+    }
+
+    let mut regressions = 0;
+
+    for comparison in &comparisons {
+        let before = format_nanos(comparison.before);
+        let after = format_nanos(comparison.after);
+
+        let Some(change) = comparison.percent_change() else {
+            println!(
+                "Day {} {}: {before} -> {after} (n/a)",
+                comparison.day, comparison.step
+            );
+            continue;
+        };
+
+        let color = if change > threshold {
+            regressions += 1;
+            ANSI_RED
+        } else if change < -threshold {
+            ANSI_GREEN
+        } else {
+            ANSI_RESET
+        };
+
+        println!(
+            "Day {} {}: {before} -> {after} {color}({change:+.1}%){ANSI_RESET}",
+            comparison.day, comparison.step,
+        );
+    }
+
+    if regressions > 0 {
+        eprintln!(
+            "\n{ANSI_RED}{regressions} step(s) regressed by more than {threshold}%.{ANSI_RESET}"
+        );
+    }
+
+    regressions > 0
+}
+
+/// Print how the timings of a day changed over the recorded benchmark history.
+pub fn handle_history(year: Year, day: Day) {
+    let records: Vec<HistoryRecord> = history::read_from_file()
+        .into_iter()
+        .filter(|r| r.timing(year, day).is_some())
+        .collect();
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+
+    if records.is_empty() {
+        println!(
+            "No benchmark history. Run `cargo time {day} --year {year} --store` to record it."
+        );
+        return;
+    }
+
+    println!(
+        "{:<16}  {:<13}  {:<8}  {:<7}  {:<18}  {:<18}  {:<18}",
+        "Date", "Commit", "Rustc", "Profile", "Parse", "Part 1", "Part 2"
+    );
+
+    let mut previous: Option<&Timing> = None;
+
+    for record in &records {
+        let Some(timing) = record.timing(year, day) else {
+            continue;
+        };
+
+        let commit = record.commit.as_deref().map_or("-".into(), |c| {
+            let hash: String = c.chars().take(7).collect();
+            if c.ends_with("-dirty") {
+                format!("{hash}-dirty")
+            } else {
+                hash
+            }
+        });
+
+        // `rustc --version` prints e.g. `rustc 1.83.0 (90b35a623 2024-11-26)`.
+        let rustc = record
+            .rustc
+            .as_deref()
+            .and_then(|r| r.split_whitespace().nth(1))
+            .unwrap_or("-");
+
+        let steps = [
+            (&timing.parse, previous.and_then(|p| p.parse.as_ref())),
+            (&timing.part_1, previous.and_then(|p| p.part_1.as_ref())),
+            (&timing.part_2, previous.and_then(|p| p.part_2.as_ref())),
+        ]
+        .map(|(current, previous)| format_step(current.as_ref(), previous));
+
+        println!(
+            "{:<16}  {commit:<13}  {rustc:<8}  {:<7}  {}  {}  {}",
+            format_timestamp(record.timestamp),
+            record.profile,
+            steps[0],
+            steps[1],
+            steps[2],
+        );
+
+        previous = Some(timing);
+    }
+}
+
+/// Formats the median of a step along with its change to the previous run, padded to a fixed visible width.
+fn format_step(current: Option<&BenchStats>, previous: Option<&BenchStats>) -> String {
+    let Some(current) = current else {
+        return format!("{:<18}", "-");
+    };
+
+    let value = format_nanos(current.median);
+
+    let Some(change) =
+        previous.and_then(|previous| percent_change(previous.median, current.median))
+    else {
+        return format!("{value:<18}");
+    };
+
+    let text = format!("{value} ({change:+.1}%)");
+    let padding = " ".repeat(18_usize.saturating_sub(text.chars().count()));
+    let color = if change > 0.0 { ANSI_RED } else { ANSI_GREEN };
+
+    format!("{value} {color}({change:+.1}%){ANSI_RESET}{padding}")
 }
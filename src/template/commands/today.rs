@@ -0,0 +1,89 @@
+use std::{
+    io::{stdout, Write},
+    thread,
+    time::Duration,
+};
+
+use chrono::Utc;
+
+use crate::template::commands::{download, read, scaffold, CommandError};
+use crate::template::{inputs, Day, Year, ANSI_BOLD, ANSI_RESET};
+
+/// Scaffold, download and read the puzzle of the current day.
+/// With `wait`, count down to the next unlock first, unless today's input has not been downloaded yet.
+pub fn handle(year: Year, wait: bool) -> Result<(), CommandError> {
+    let day = match Day::today() {
+        Some(day) if !wait || !inputs::exists(year, day) => day,
+        _ if wait => wait_for_unlock(year)?,
+        _ => {
+            return Err(CommandError::Usage(
+                "`today` command can only be run between the 1st and \
+                the 25th of december. Please use `scaffold` with a specific day, \
+                or pass `--wait` to wait for the next puzzle."
+                    .into(),
+            ));
+        }
+    };
+
+    scaffold::handle(year, day, false)?;
+
+    if wait {
+        download::handle_with_backoff(year, day)?;
+    } else {
+        download::handle(year, day, false)?;
+    }
+
+    scaffold::fill_expected_answers(year, day)?;
+    read::handle(year, day)
+}
+
+/// Shows a countdown until the next puzzle unlocks and returns its day.
+fn wait_for_unlock(year: Year) -> Result<Day, CommandError> {
+    let Some((unlock_year, day, unlock)) = Day::next_unlock() else {
+        return Err(CommandError::Data(
+            "Could not tell when the next puzzle unlocks.".into(),
+        ));
+    };
+
+    if unlock_year != year {
+        return Err(CommandError::Usage(format!(
+            "The next puzzle unlocks in {unlock_year}, but the year is set to {year}. \
+            Pass `--year {unlock_year}` or update AOC_YEAR."
+        )));
+    }
+
+    loop {
+        let remaining = (unlock - Utc::now().fixed_offset()).num_seconds();
+
+        if remaining <= 0 {
+            break;
+        }
+
+        print!(
+            "\r\x1b[2K🎄 Day {day} unlocks in {ANSI_BOLD}{}{ANSI_RESET}",
+            format_countdown(remaining)
+        );
+        let _ = stdout().flush();
+
+        thread::sleep(Duration::from_secs(1));
+    }
+
+    println!("\r\x1b[2K🎄 Day {day} is unlocked!");
+    Ok(day)
+}
+
+/// Formats the seconds left as `1d 02:03:04`, leaving out the days if there are none.
+fn format_countdown(secs: i64) -> String {
+    let (days, hours, minutes, secs) = (
+        secs / 86_400,
+        secs % 86_400 / 3600,
+        secs % 3600 / 60,
+        secs % 60,
+    );
+
+    if days > 0 {
+        format!("{days}d {hours:02}:{minutes:02}:{secs:02}")
+    } else {
+        format!("{hours:02}:{minutes:02}:{secs:02}")
+    }
+}
@@ -0,0 +1,75 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use crate::template::answers::{Answers, Verification};
+use crate::template::commands::CommandError;
+use crate::template::run_multi::run_days;
+use crate::template::{
+    Day, DaySet, Year, ANSI_BOLD, ANSI_GREEN, ANSI_ITALIC, ANSI_RED, ANSI_RESET,
+};
+
+/// Run the given days, or all days with recorded answers, and check their answers against the `answers.json` of the year.
+/// Fails if any answer does not match.
+pub fn handle(
+    year: Year,
+    days: Option<DaySet>,
+    timeout: Option<Duration>,
+) -> Result<(), CommandError> {
+    let answers = Answers::read_from_file(year);
+
+    let days: Vec<Day> = match days {
+        Some(days) => days.resolve(year),
+        None => answers.days(),
+    };
+
+    if days.is_empty() {
+        println!(
+            "No answers recorded in \"{}/answers.json\" yet.",
+            year.data_dir()
+        );
+        return Ok(());
+    }
+
+    let days_to_run: HashSet<Day> = days.iter().copied().collect();
+    let reports = run_days(year, &days_to_run, true, false, timeout);
+
+    println!("\n{ANSI_BOLD}Verification{ANSI_RESET}");
+
+    let mut mismatches = 0;
+
+    for day in days {
+        for part in [1, 2] {
+            let report = reports.iter().find(|r| r.day == day && r.part == part);
+            let answer = report.and_then(|r| r.answer.as_deref());
+
+            // skip parts that neither ran nor have a recorded answer, e.g. the missing part 2 of day 25.
+            if report.is_none() && answers.get(day, part).is_none() {
+                continue;
+            }
+
+            let label = format!("Day {day} Part {part}");
+
+            match answers.verify(day, part, answer) {
+                Verification::Match => println!("{label}: {ANSI_GREEN}match{ANSI_RESET}"),
+                Verification::Mismatch { expected } => {
+                    mismatches += 1;
+                    println!(
+                        "{label}: {ANSI_RED}mismatch{ANSI_RESET} (expected {expected}, got {})",
+                        answer.unwrap_or("nothing")
+                    );
+                }
+                Verification::Missing => {
+                    println!("{label}: {ANSI_ITALIC}missing{ANSI_RESET}");
+                }
+            }
+        }
+    }
+
+    if mismatches > 0 {
+        return Err(CommandError::Check(format!(
+            "{mismatches} answer(s) do not match."
+        )));
+    }
+
+    Ok(())
+}
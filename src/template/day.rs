@@ -3,10 +3,13 @@ use std::fmt::Display;
 use std::str::FromStr;
 
 #[cfg(feature = "today")]
-use chrono::{Datelike, FixedOffset, Utc};
+use chrono::{DateTime, Datelike, FixedOffset, TimeZone, Utc};
 
 #[cfg(feature = "today")]
-const SERVER_UTC_OFFSET: i32 = -5;
+use crate::template::Year;
+
+/// Offset of the timezone of the Advent of Code server in hours, puzzles unlock at midnight there.
+pub(crate) const SERVER_UTC_OFFSET: i32 = -5;
 
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
 ///
@@ -55,6 +58,27 @@ impl Day {
             None
         }
     }
+
+    /// Returns the next puzzle to unlock, along with its year and the time it unlocks at.
+    /// Puzzles unlock at midnight in the server's timezone.
+    pub fn next_unlock() -> Option<(Year, Self, DateTime<FixedOffset>)> {
+        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
+        let now = Utc::now().with_timezone(&offset);
+
+        let (year, day) = match (now.month(), now.day()) {
+            (12, day) if day < 25 => (now.year(), day + 1),
+            (12, _) => (now.year() + 1, 1),
+            _ => (now.year(), 1),
+        };
+
+        let unlock = offset.with_ymd_and_hms(year, 12, day, 0, 0, 0).single()?;
+
+        Some((
+            Year::new(u16::try_from(year).ok()?)?,
+            Self::new(u8::try_from(day).ok()?)?,
+            unlock,
+        ))
+    }
 }
 
 impl Display for Day {
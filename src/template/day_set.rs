@@ -0,0 +1,177 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::timings::Timings;
+use crate::template::{all_days, Day, Year};
+
+/// A set of days, parsed from a comma-separated list of days, inclusive ranges and keywords.
+///
+/// - `7` or `07`: a single day.
+/// - `1..5` or `1..=5`: the days 1 to 5.
+/// - `all`: every day.
+/// - `solved` / `unsolved`: days that have / do not have timings for both parts in `data/timings.json`.
+///
+/// ```
+/// # use advent_of_code::template::{DaySet, Year};
+/// let set: DaySet = "1..3,7".parse().unwrap();
+/// let days = set.resolve(Year::new(2024).unwrap());
+/// assert_eq!(days.len(), 4);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaySet(Vec<Selector>);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Selector {
+    Range(Day, Day),
+    All,
+    Solved,
+    Unsolved,
+}
+
+impl DaySet {
+    /// A set that holds a single day.
+    pub fn single(day: Day) -> Self {
+        Self(vec![Selector::Range(day, day)])
+    }
+
+    /// A set that holds every day.
+    pub fn all() -> Self {
+        Self(vec![Selector::All])
+    }
+
+    /// Returns the days of the set in ascending order. `solved` and `unsolved` are looked up in the stored timings of the year.
+    pub fn resolve(&self, year: Year) -> Vec<Day> {
+        let needs_timings = self
+            .0
+            .iter()
+            .any(|s| matches!(s, Selector::Solved | Selector::Unsolved));
+
+        let timings = if needs_timings {
+            Timings::read_from_file()
+        } else {
+            Timings::default()
+        };
+
+        self.resolve_with(|day| timings.is_day_complete(year, day))
+    }
+
+    fn resolve_with(&self, is_solved: impl Fn(Day) -> bool) -> Vec<Day> {
+        all_days()
+            .filter(|day| {
+                self.0.iter().any(|selector| match selector {
+                    Selector::Range(start, end) => (start..=end).contains(&day),
+                    Selector::All => true,
+                    Selector::Solved => is_solved(*day),
+                    Selector::Unsolved => !is_solved(*day),
+                })
+            })
+            .collect()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for DaySet {
+    type Err = DaySetFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || DaySetFromStrError(s.to_string());
+
+        let selectors = s
+            .split(',')
+            .map(|item| {
+                let item = item.trim();
+
+                match item {
+                    "all" => return Ok(Selector::All),
+                    "solved" => return Ok(Selector::Solved),
+                    "unsolved" => return Ok(Selector::Unsolved),
+                    _ => {}
+                }
+
+                let (start, end) = match item.split_once("..") {
+                    Some((start, end)) => (start, end.strip_prefix('=').unwrap_or(end)),
+                    None => (item, item),
+                };
+
+                let start: Day = start.trim().parse().map_err(|_| error())?;
+                let end: Day = end.trim().parse().map_err(|_| error())?;
+
+                if start > end {
+                    return Err(error());
+                }
+
+                Ok(Selector::Range(start, end))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self(selectors))
+    }
+}
+
+/// An error which can be returned when parsing a [`DaySet`].
+#[derive(Debug)]
+pub struct DaySetFromStrError(String);
+
+impl Error for DaySetFromStrError {}
+
+impl Display for DaySetFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid days `{}`, expecting e.g. `1..5,7,12`, `all`, `solved` or `unsolved`",
+            self.0
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::DaySet;
+    use crate::day;
+    use crate::template::Day;
+
+    fn days(s: &str, solved: &[Day]) -> Vec<u8> {
+        s.parse::<DaySet>()
+            .unwrap()
+            .resolve_with(|day| solved.contains(&day))
+            .into_iter()
+            .map(Day::into_inner)
+            .collect()
+    }
+
+    #[test]
+    fn parses_days_and_ranges() {
+        assert_eq!(days("7", &[]), vec![7]);
+        assert_eq!(days("1..5,7,12", &[]), vec![1, 2, 3, 4, 5, 7, 12]);
+        assert_eq!(days("12, 03..=4, 3", &[]), vec![3, 4, 12]);
+        assert_eq!(days("all", &[]).len(), 25);
+    }
+
+    #[test]
+    fn resolves_solved_days() {
+        let solved = [day!(1), day!(2), day!(4)];
+        assert_eq!(days("solved", &solved), vec![1, 2, 4]);
+        assert_eq!(days("unsolved", &solved).len(), 22);
+        assert_eq!(days("unsolved,1..3", &solved)[..3], [1, 2, 3]);
+    }
+
+    #[test]
+    fn rejects_invalid_sets() {
+        for s in ["", "0", "26", "5..1", "1..", "1,,2", "some"] {
+            assert!(s.parse::<DaySet>().is_err(), "{s}");
+        }
+    }
+
+    #[test]
+    fn builds_single_sets() {
+        assert_eq!(
+            DaySet::single(day!(3)).resolve_with(|_| false),
+            vec![day!(3)]
+        );
+        assert_eq!(DaySet::all().resolve_with(|_| false).len(), 25);
+    }
+}
@@ -0,0 +1,351 @@
+/// Extraction of example inputs and answers from puzzle descriptions, as written by `download` and `read`.
+/// Examples are the fenced code blocks of the markdown. Blocks after the `--- Part Two ---` heading belong to part 2.
+/// Example answers are the last highlighted code of each part, e.g. `` `*143*` ``.
+use crate::template::{Day, Year};
+
+static PART_TWO_HEADING: &str = "--- Part Two ---";
+
+/// Assertion of the scaffolded tests before an expected answer is known.
+static EMPTY_ASSERTION: &str = "assert_eq!(result, None);";
+
+/// A fenced code block of a puzzle description.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CodeBlock {
+    /// Part of the puzzle whose section contains the block.
+    pub part: u8,
+    pub content: String,
+}
+
+impl CodeBlock {
+    pub fn line_count(&self) -> usize {
+        self.content.lines().count()
+    }
+}
+
+/// Finds all fenced code blocks in a puzzle description, in order.
+pub fn code_blocks(markdown: &str) -> Vec<CodeBlock> {
+    let mut blocks = vec![];
+    let mut part = 1;
+    let mut current: Option<String> = None;
+
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            match current.take() {
+                Some(content) => blocks.push(CodeBlock { part, content }),
+                None => current = Some(String::new()),
+            }
+            continue;
+        }
+
+        match current.as_mut() {
+            Some(content) => {
+                content.push_str(line);
+                content.push('\n');
+            }
+            None if line.starts_with('#') && line.contains(PART_TWO_HEADING) => part = 2,
+            None => {}
+        }
+    }
+
+    blocks
+}
+
+/// Picks the block that most likely is the example input of a part, returning its index.
+/// Example inputs usually are the first block of a section that spans multiple lines, while
+/// single-line blocks tend to be snippets or intermediate results. If part 2 has no block of its own,
+/// it shares the example of part 1.
+pub fn pick(blocks: &[CodeBlock], part: u8) -> Option<usize> {
+    let in_part = |part: u8| {
+        blocks
+            .iter()
+            .enumerate()
+            .filter(move |(_, block)| block.part == part)
+    };
+
+    let picked = in_part(part)
+        .find(|(_, block)| block.line_count() > 1)
+        .or_else(|| in_part(part).next())
+        .map(|(i, _)| i);
+
+    match picked {
+        None if part == 2 => pick(blocks, 1),
+        picked => picked,
+    }
+}
+
+/// Finds the highlighted example answer of each part, i.e. the last emphasized code span of its section.
+pub fn expected_answers(markdown: &str) -> [Option<String>; 2] {
+    let mut answers = [None, None];
+    let mut part: u8 = 1;
+    let mut in_block = false;
+
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            in_block = !in_block;
+            continue;
+        }
+
+        if in_block {
+            continue;
+        }
+
+        if line.starts_with('#') && line.contains(PART_TWO_HEADING) {
+            part = 2;
+            continue;
+        }
+
+        if let Some(answer) = highlights(line).last() {
+            answers[usize::from(part - 1)] = Some(answer.to_string());
+        }
+    }
+
+    answers
+}
+
+/// Emphasized code spans of a line, written either as `` `*x*` `` or as `` *`x`* ``.
+fn highlights(line: &str) -> Vec<&str> {
+    let mut found = vec![];
+    let mut rest = line;
+
+    while let Some(start) = rest.find("`*").into_iter().chain(rest.find("*`")).min() {
+        let closing = if rest[start..].starts_with("`*") {
+            "*`"
+        } else {
+            "`*"
+        };
+
+        let content = &rest[start + 2..];
+
+        let Some(end) = content.find(closing) else {
+            break;
+        };
+
+        if end > 0 {
+            found.push(&content[..end]);
+        }
+
+        rest = &content[end + 2..];
+    }
+
+    found
+}
+
+/// Replaces the `None` assertion in the scaffolded test of a part with the expected answer.
+/// Returns `None` if the test does not exist or its assertion was already changed.
+pub fn fill_expected_answer(source: &str, part: u8, answer: &str) -> Option<String> {
+    let (test_start, test) = find_test(source, part)?;
+    let assertion = test.find(EMPTY_ASSERTION)? + test_start;
+
+    Some(format!(
+        "{}assert_eq!(result, Some({answer})){}",
+        &source[..assertion],
+        &source[assertion + EMPTY_ASSERTION.len() - 1..]
+    ))
+}
+
+/// Reads the expected answer from the `assert_eq!(result, Some(..))` of the scaffolded test of a part.
+/// String answers like `Some("abc".to_string())` are unquoted.
+pub fn tested_answer(source: &str, part: u8) -> Option<String> {
+    let (_, test) = find_test(source, part)?;
+
+    let start = test.find("assert_eq!(result, Some(")? + "assert_eq!(result, Some(".len();
+    let end = test[start..].find(");")? + start;
+    // NOTE: `end` points behind the closing paren of `assert_eq!`, the one of `Some` comes right before it.
+    let answer = test[start..end].trim().strip_suffix(')')?.trim();
+
+    let answer = [".to_string()", ".to_owned()", ".into()"]
+        .iter()
+        .fold(answer, |answer, suffix| {
+            answer.strip_suffix(suffix).unwrap_or(answer)
+        });
+    let answer = answer.trim_matches('"');
+
+    if answer.is_empty() {
+        None
+    } else {
+        Some(answer.to_string())
+    }
+}
+
+/// Finds the scaffolded test of a part, returning its offset in `source` and its source up to the next function.
+fn find_test(source: &str, part: u8) -> Option<(usize, &str)> {
+    let test_name = if part == 1 {
+        "fn test_part_one()"
+    } else {
+        "fn test_part_two()"
+    };
+
+    let test_start = source.find(test_name)?;
+    let test_len = source[test_start + 1..]
+        .find("fn ")
+        .map_or(source.len() - test_start, |x| x + 1);
+
+    Some((test_start, &source[test_start..test_start + test_len]))
+}
+
+/// Paths to write the examples of both parts to. A single file is used when both parts share an example,
+/// otherwise the per-part files read by `read_file_part` are written as well.
+pub fn example_paths(year: Year, day: Day, is_shared: bool) -> Vec<(u8, String)> {
+    let path = year.data_path("examples", day);
+
+    if is_shared {
+        return vec![(1, path)];
+    }
+
+    let dir = format!("{}/examples", year.data_dir());
+
+    vec![
+        (1, path),
+        (1, format!("{dir}/{day}-1.txt")),
+        (2, format!("{dir}/{day}-2.txt")),
+    ]
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        code_blocks, example_paths, expected_answers, fill_expected_answer, pick, tested_answer,
+        CodeBlock,
+    };
+    use crate::{day, year};
+
+    const PUZZLE: &str = "## --- Day 3: Mull It Over ---
+
+The computer appears to be trying to run a program, like `mul(44,46)`:
+
+```
+xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))
+```
+
+Only the four highlighted sections are real `mul` instructions. Adding up the result of each instruction produces `*161*`.
+
+## --- Part Two ---
+
+For example:
+
+```
+mul(2,4)
+```
+
+```
+xmul(2,4)&mul[3,7]!^don't()_mul(5,5)
++mul(32,64](mul(11,8)undo()?mul(8,5))
+```
+
+This time, the sum of the results is *`48`* (`2*4 + 8*5`).
+";
+
+    const MODULE: &str = "advent_of_code::solution!(3);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file(\"examples\", DAY));
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file(\"examples\", DAY));
+        assert_eq!(result, None);
+    }
+}
+";
+
+    fn block(part: u8, content: &str) -> CodeBlock {
+        CodeBlock {
+            part,
+            content: content.into(),
+        }
+    }
+
+    #[test]
+    fn finds_code_blocks() {
+        assert_eq!(
+            code_blocks(PUZZLE),
+            vec![
+                block(
+                    1,
+                    "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))\n"
+                ),
+                block(2, "mul(2,4)\n"),
+                block(
+                    2,
+                    "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)\n+mul(32,64](mul(11,8)undo()?mul(8,5))\n"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn picks_examples() {
+        let blocks = code_blocks(PUZZLE);
+        assert_eq!(pick(&blocks, 1), Some(0));
+        assert_eq!(pick(&blocks, 2), Some(2));
+    }
+
+    #[test]
+    fn shares_examples_without_part_two_blocks() {
+        let blocks = vec![block(1, "a\n"), block(1, "1 2\n3 4\n")];
+        assert_eq!(pick(&blocks, 1), Some(1));
+        assert_eq!(pick(&blocks, 2), Some(1));
+        assert_eq!(pick(&[], 1), None);
+    }
+
+    #[test]
+    fn finds_expected_answers() {
+        assert_eq!(
+            expected_answers(PUZZLE),
+            [Some("161".to_string()), Some("48".to_string())]
+        );
+        assert_eq!(
+            expected_answers("Only `*1*` part."),
+            [Some("1".into()), None]
+        );
+    }
+
+    #[test]
+    fn fills_expected_answers() {
+        let filled = fill_expected_answer(MODULE, 2, "48").unwrap();
+        assert!(filled
+            .contains("assert_eq!(result, None);\n    }\n\n    #[test]\n    fn test_part_two"));
+        assert!(filled.contains("assert_eq!(result, Some(48));\n    }\n}"));
+
+        let filled = fill_expected_answer(&filled, 1, "161").unwrap();
+        assert!(filled.contains("assert_eq!(result, Some(161));"));
+        assert_eq!(fill_expected_answer(&filled, 1, "161"), None);
+    }
+
+    #[test]
+    fn reads_tested_answers() {
+        assert_eq!(tested_answer(MODULE, 1), None);
+
+        let filled = fill_expected_answer(MODULE, 2, "48").unwrap();
+        assert_eq!(tested_answer(&filled, 2), Some("48".into()));
+        assert_eq!(tested_answer(&filled, 1), None);
+
+        let source = "fn test_part_one() {\n    assert_eq!(result, Some(\"abc\".to_string()));\n}";
+        assert_eq!(tested_answer(source, 1), Some("abc".into()));
+    }
+
+    #[test]
+    fn builds_example_paths() {
+        assert_eq!(
+            example_paths(year!(2015), day!(3), true),
+            vec![(1, "data/2015/examples/03.txt".to_string())]
+        );
+        assert_eq!(
+            example_paths(year!(2015), day!(3), false),
+            vec![
+                (1, "data/2015/examples/03.txt".to_string()),
+                (1, "data/2015/examples/03-1.txt".to_string()),
+                (2, "data/2015/examples/03-2.txt".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,266 @@
+/// Append-only log of benchmark runs, which allows tracking the performance of a day over time.
+/// Every line of the log is a JSON object holding the timings of a run alongside metadata about the build.
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{Error, Write},
+    process::Command,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::timings::{Timing, Timings};
+use crate::template::{Day, Year};
+
+static HISTORY_FILE_PATH: &str = "./data/timings-history.jsonl";
+
+/// A single benchmark run.
+#[derive(Clone, Debug)]
+pub struct HistoryRecord {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Hash of the git `HEAD`, suffixed with `-dirty` if the working tree had changes.
+    pub commit: Option<String>,
+    /// Output of `rustc --version`.
+    pub rustc: Option<String>,
+    /// Build profile of the binary that recorded the run.
+    pub profile: String,
+    pub timings: Timings,
+}
+
+impl HistoryRecord {
+    /// Creates a record for the given timings, capturing metadata of the current environment.
+    pub fn capture(timings: Timings) -> Self {
+        let commit =
+            command_output("git", &["rev-parse", "HEAD"]).map(|hash| {
+                match command_output("git", &["status", "--porcelain"]) {
+                    Some(_) => format!("{hash}-dirty"),
+                    None => hash,
+                }
+            });
+
+        let profile = if cfg!(debug_assertions) {
+            "debug"
+        } else {
+            "release"
+        };
+
+        Self {
+            timestamp: unix_now(),
+            commit,
+            rustc: command_output("rustc", &["--version"]),
+            profile: profile.into(),
+            timings,
+        }
+    }
+
+    /// Timing of the given day in this run, if it was benched.
+    pub fn timing(&self, year: Year, day: Day) -> Option<&Timing> {
+        self.timings
+            .data
+            .iter()
+            .find(|t| t.year == year && t.day == day)
+    }
+}
+
+/// Append a record to the history file.
+pub fn append(record: &HistoryRecord) -> Result<(), Error> {
+    let line = JsonValue::from(record)
+        .stringify()
+        .map_err(|e| Error::new(std::io::ErrorKind::InvalidData, e.to_string()))?;
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(HISTORY_FILE_PATH)?;
+
+    writeln!(file, "{line}")
+}
+
+/// Read all records from the history file, oldest first. Lines that can not be parsed are skipped.
+pub fn read_from_file() -> Vec<HistoryRecord> {
+    fs::read_to_string(HISTORY_FILE_PATH)
+        .map(|content| {
+            content
+                .lines()
+                .filter_map(|line| HistoryRecord::from_str(line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Seconds since the unix epoch.
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Runs a command and returns its trimmed stdout if it succeeded and printed something.
+fn command_output(cmd: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(cmd).args(args).output().ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if stdout.is_empty() {
+        None
+    } else {
+        Some(stdout)
+    }
+}
+
+/// Formats a unix timestamp as UTC date and time, e.g. `2024-12-06 10:42`.
+pub fn format_timestamp(timestamp: u64) -> String {
+    let days = i64::try_from(timestamp / 86_400).unwrap_or_default();
+    let secs_of_day = timestamp % 86_400;
+
+    // see: http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        secs_of_day / 3600,
+        (secs_of_day % 3600) / 60
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HistoryRecord> for JsonValue {
+    fn from(value: &HistoryRecord) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let optional = |x: &Option<String>| match x {
+            Some(x) => JsonValue::String(x.clone()),
+            None => JsonValue::Null,
+        };
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert("commit".into(), optional(&value.commit));
+        map.insert("rustc".into(), optional(&value.rustc));
+        map.insert("profile".into(), JsonValue::String(value.profile.clone()));
+        map.insert("timings".into(), JsonValue::from(value.timings.clone()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl FromStr for HistoryRecord {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).or(Err("not valid JSON line."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history record to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u64)
+            .ok_or("Expected record.timestamp to be a number.")?;
+
+        let optional = |key: &str| {
+            json.get(key)
+                .map(|v| v.get::<String>().cloned())
+                .ok_or(format!("Expected record.{key} to be null or string."))
+        };
+
+        let profile = json
+            .get("profile")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected record.profile to be a string.")?;
+
+        let timings = json
+            .get("timings")
+            .ok_or("Expected record to have key `timings`.")
+            .map(Timings::try_from)??;
+
+        Ok(HistoryRecord {
+            timestamp,
+            commit: optional("commit")?,
+            rustc: optional("rustc")?,
+            profile: profile.clone(),
+            timings,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_timestamp, HistoryRecord};
+    use crate::{
+        day,
+        template::{
+            stats::BenchStats,
+            timings::{Timing, Timings},
+        },
+        year,
+    };
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn roundtrips_records() {
+        let record = HistoryRecord {
+            timestamp: 1_733_479_320,
+            commit: Some("1a2b3c4".into()),
+            rustc: None,
+            profile: "release".into(),
+            timings: Timings {
+                data: vec![Timing {
+                    year: year!(2024),
+                    day: day!(6),
+                    parse: None,
+                    part_1: Some(BenchStats::from_nanos(538_100.0)),
+                    part_2: None,
+                    memory_1: None,
+                    memory_2: None,
+                    timed_out: None,
+                    total_nanos: 538_100.0,
+                }],
+            },
+        };
+
+        let line = JsonValue::from(&record).stringify().unwrap();
+        let parsed: HistoryRecord = line.parse().unwrap();
+
+        assert_eq!(parsed.timestamp, record.timestamp);
+        assert_eq!(parsed.commit, record.commit);
+        assert_eq!(parsed.rustc, None);
+        assert_eq!(parsed.profile, "release");
+        assert_eq!(
+            parsed.timing(year!(2024), day!(6)).unwrap().part_1,
+            Some(BenchStats::from_nanos(538_100.0))
+        );
+        assert!(parsed.timing(year!(2024), day!(7)).is_none());
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(1_733_479_320), "2024-12-06 10:02");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00");
+    }
+}
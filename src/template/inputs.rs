@@ -0,0 +1,230 @@
+/// Checksums of downloaded puzzle inputs, which make it possible to tell when an input was emptied or changed by hand.
+/// A checksum is recorded whenever an input is downloaded. Inputs that were pasted in manually are not tracked.
+use std::{collections::HashMap, fs, io::Error, str::FromStr};
+
+use tinyjson::JsonValue;
+
+use crate::template::{Day, Year};
+
+static CHECKSUMS_FILE_NAME: &str = "input-checksums.json";
+
+/// A downloaded input.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InputRecord {
+    pub checksum: String,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+/// State of an input file compared to its recorded checksum.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InputStatus {
+    Ok,
+    Missing,
+    Empty,
+    /// The input differs from the downloaded one.
+    Modified,
+    /// The input was not downloaded, so there is nothing to compare it to.
+    Untracked,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InputChecksums {
+    data: HashMap<Day, InputRecord>,
+}
+
+impl InputChecksums {
+    /// Dehydrate checksums to the JSON file of a year.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(checksums_path(year))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate checksums from the JSON file of a year. If not present, returns empty checksums.
+    pub fn read_from_file(year: Year) -> Self {
+        fs::read_to_string(checksums_path(year))
+            .map_err(|x| x.to_string())
+            .and_then(|s| InputChecksums::from_str(&s))
+            .unwrap_or_default()
+    }
+
+    pub fn get(&self, day: Day) -> Option<&InputRecord> {
+        self.data.get(&day)
+    }
+
+    /// Records the checksum of a freshly downloaded input.
+    pub fn set(&mut self, day: Day, contents: &[u8], timestamp: u64) {
+        self.data.insert(
+            day,
+            InputRecord {
+                checksum: checksum(contents),
+                timestamp,
+            },
+        );
+    }
+
+    /// Compares the contents of an input file, `None` if it does not exist, to the recorded checksum.
+    pub fn status(&self, day: Day, contents: Option<&[u8]>) -> InputStatus {
+        match (contents, self.get(day)) {
+            (None, _) => InputStatus::Missing,
+            (Some(contents), _) if contents.iter().all(u8::is_ascii_whitespace) => {
+                InputStatus::Empty
+            }
+            (Some(_), None) => InputStatus::Untracked,
+            (Some(contents), Some(record)) if record.checksum == checksum(contents) => {
+                InputStatus::Ok
+            }
+            (Some(_), Some(_)) => InputStatus::Modified,
+        }
+    }
+}
+
+fn checksums_path(year: Year) -> String {
+    format!("{}/{CHECKSUMS_FILE_NAME}", year.data_dir())
+}
+
+/// Checks the input file of a day against its recorded checksum.
+pub fn check(year: Year, day: Day) -> InputStatus {
+    let contents = fs::read(year.data_path("inputs", day)).ok();
+    InputChecksums::read_from_file(year).status(day, contents.as_deref())
+}
+
+/// Returns `true` if the input file of a day exists and is not empty.
+pub fn exists(year: Year, day: Day) -> bool {
+    !matches!(check(year, day), InputStatus::Missing | InputStatus::Empty)
+}
+
+/// Records the checksum of the input file of a day, after it was downloaded.
+pub fn record(year: Year, day: Day, timestamp: u64) -> Result<(), Error> {
+    let contents = fs::read(year.data_path("inputs", day))?;
+    let mut checksums = InputChecksums::read_from_file(year);
+    checksums.set(day, &contents, timestamp);
+    checksums.store_file(year)
+}
+
+/// 64-bit FNV-1a hash, hex encoded. Good enough to detect changes and does not need a dependency.
+pub fn checksum(contents: &[u8]) -> String {
+    let hash = contents
+        .iter()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+
+    format!("{hash:016x}")
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&InputChecksums> for JsonValue {
+    fn from(value: &InputChecksums) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        for (day, record) in &value.data {
+            let mut record_map: HashMap<String, JsonValue> = HashMap::new();
+
+            record_map.insert(
+                "checksum".into(),
+                JsonValue::String(record.checksum.clone()),
+            );
+            #[allow(clippy::cast_precision_loss)]
+            record_map.insert(
+                "timestamp".into(),
+                JsonValue::Number(record.timestamp as f64),
+            );
+
+            map.insert(day.to_string(), JsonValue::Object(record_map));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl FromStr for InputChecksums {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected input checksums to be a JSON object.")?;
+
+        let mut data = HashMap::new();
+
+        for (key, value) in json {
+            let day = Day::from_str(key).or(Err(format!("Expected `{key}` to be a day.")))?;
+
+            let record = value
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or(format!("Expected checksums.{key} to be a JSON object."))?;
+
+            let checksum = record
+                .get("checksum")
+                .and_then(|v| v.get::<String>())
+                .ok_or(format!("Expected checksums.{key}.checksum to be a string."))?;
+
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let timestamp = record
+                .get("timestamp")
+                .and_then(|v| v.get::<f64>())
+                .map(|x| *x as u64)
+                .ok_or(format!(
+                    "Expected checksums.{key}.timestamp to be a number."
+                ))?;
+
+            data.insert(
+                day,
+                InputRecord {
+                    checksum: checksum.clone(),
+                    timestamp,
+                },
+            );
+        }
+
+        Ok(InputChecksums { data })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{checksum, InputChecksums, InputStatus};
+    use crate::day;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn computes_checksums() {
+        assert_eq!(checksum(b""), "cbf29ce484222325");
+        assert_eq!(checksum(b"a"), "af63dc4c8601ec8c");
+        assert_ne!(checksum(b"1 2\n"), checksum(b"1 2\n\n"));
+    }
+
+    #[test]
+    fn detects_input_status() {
+        let mut checksums = InputChecksums::default();
+        checksums.set(day!(1), b"1 2\n", 1_733_029_200);
+
+        assert_eq!(checksums.status(day!(1), Some(b"1 2\n")), InputStatus::Ok);
+        assert_eq!(
+            checksums.status(day!(1), Some(b"1 3\n")),
+            InputStatus::Modified
+        );
+        assert_eq!(checksums.status(day!(1), Some(b"\n")), InputStatus::Empty);
+        assert_eq!(checksums.status(day!(1), None), InputStatus::Missing);
+        assert_eq!(
+            checksums.status(day!(2), Some(b"1 2\n")),
+            InputStatus::Untracked
+        );
+    }
+
+    #[test]
+    fn roundtrips_checksums() {
+        let mut checksums = InputChecksums::default();
+        checksums.set(day!(6), b"....#.....\n", 1_733_029_200);
+
+        let json = JsonValue::from(&checksums).stringify().unwrap();
+        assert_eq!(json.parse::<InputChecksums>().unwrap(), checksums);
+    }
+}
@@ -0,0 +1,276 @@
+/// Private leaderboards, as served by `/<year>/leaderboard/private/view/<id>.json`.
+/// Only the fields needed to rank members and compare their solve times are read.
+use std::{collections::HashMap, str::FromStr};
+
+use tinyjson::JsonValue;
+
+use crate::template::{Day, Year};
+
+/// A member of a private leaderboard.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Member {
+    pub id: u64,
+    /// `None` for anonymous users.
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u32,
+    /// Seconds since the unix epoch, `0` if the member has no stars yet.
+    pub last_star_ts: u64,
+    /// Timestamps at which the stars of both parts of a day were collected.
+    pub completions: HashMap<Day, [Option<u64>; 2]>,
+}
+
+impl Member {
+    /// Name of the member, in the same format the website uses for anonymous users.
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    /// Timestamp at which the star of a part was collected, if it was.
+    pub fn star_ts(&self, day: Day, part: u8) -> Option<u64> {
+        *self
+            .completions
+            .get(&day)?
+            .get(usize::from(part).checked_sub(1)?)?
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Leaderboard {
+    pub members: Vec<Member>,
+}
+
+impl Leaderboard {
+    /// Members ordered like the website does: by local score, then by who got there first.
+    pub fn ranked(&self) -> Vec<&Member> {
+        let mut members: Vec<&Member> = self.members.iter().collect();
+        members.sort_by(|a, b| {
+            b.local_score
+                .cmp(&a.local_score)
+                .then(b.stars.cmp(&a.stars))
+                .then(a.last_star_ts.cmp(&b.last_star_ts))
+                .then(a.id.cmp(&b.id))
+        });
+        members
+    }
+
+    /// Members that solved part 2 of a day, fastest first, along with the seconds it took them since the puzzle unlocked.
+    pub fn part_two_times(&self, year: Year, day: Day) -> Vec<(&Member, u64)> {
+        let unlock = year.unlock_timestamp(day);
+
+        let mut times: Vec<(&Member, u64)> = self
+            .members
+            .iter()
+            .filter_map(|m| Some((m, m.star_ts(day, 2)?.saturating_sub(unlock))))
+            .collect();
+
+        times.sort_by_key(|(m, elapsed)| (*elapsed, m.id));
+        times
+    }
+}
+
+/// Formats the time it took to solve a puzzle, e.g. `1h 02m 03s`.
+pub fn format_elapsed(secs: u64) -> String {
+    let (days, hours, minutes, secs) = (
+        secs / 86_400,
+        secs % 86_400 / 3600,
+        secs % 3600 / 60,
+        secs % 60,
+    );
+
+    if days > 0 {
+        format!("{days}d {hours:02}h {minutes:02}m")
+    } else if hours > 0 {
+        format!("{hours}h {minutes:02}m {secs:02}s")
+    } else {
+        format!("{minutes}m {secs:02}s")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Leaderboard {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).or(Err("not valid JSON file."))?;
+
+        let members = json
+            .get::<HashMap<String, JsonValue>>()
+            .and_then(|x| x.get("members"))
+            .and_then(|x| x.get::<HashMap<String, JsonValue>>())
+            .ok_or("Expected leaderboard.members to be an object.")?;
+
+        Ok(Leaderboard {
+            members: members
+                .values()
+                .map(Member::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl TryFrom<&JsonValue> for Member {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected member to be an object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(timestamp)
+                .ok_or(format!("Expected member.{key} to be a number."))
+        };
+
+        let name = match json.get("name") {
+            Some(JsonValue::String(name)) => Some(name.clone()),
+            Some(JsonValue::Null) | None => None,
+            Some(_) => return Err("Expected member.name to be null or string.".into()),
+        };
+
+        let days = json
+            .get("completion_day_level")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or("Expected member.completion_day_level to be an object.")?;
+
+        let mut completions = HashMap::new();
+
+        for (day, parts) in days {
+            let day: Day = day
+                .parse()
+                .map_err(|_| format!("Expected member.completion_day_level.{day} to be a day."))?;
+
+            let parts = parts.get::<HashMap<String, JsonValue>>().ok_or(format!(
+                "Expected member.completion_day_level.{day} to be an object."
+            ))?;
+
+            let star = |part: &str| {
+                parts
+                    .get(part)
+                    .map(|x| {
+                        x.get::<HashMap<String, JsonValue>>()
+                            .and_then(|x| x.get("get_star_ts"))
+                            .and_then(timestamp)
+                            .ok_or(format!(
+                                "Expected member.completion_day_level.{day}.{part}.get_star_ts to be a number."
+                            ))
+                    })
+                    .transpose()
+            };
+
+            completions.insert(day, [star("1")?, star("2")?]);
+        }
+
+        #[allow(clippy::cast_possible_truncation)]
+        Ok(Member {
+            id: number("id")?,
+            name,
+            stars: number("stars")? as u32,
+            local_score: number("local_score")? as u32,
+            last_star_ts: number("last_star_ts")?,
+            completions,
+        })
+    }
+}
+
+/// Reads a non-negative integer. Older leaderboards serve some of them as strings.
+fn timestamp(value: &JsonValue) -> Option<u64> {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    match value {
+        JsonValue::Number(x) => Some(*x as u64),
+        JsonValue::String(x) => x.parse().ok(),
+        _ => None,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_elapsed, Leaderboard};
+    use crate::{day, year};
+
+    // 2024-12-01 05:00 UTC is 1733029200.
+    const LEADERBOARD: &str = r#"{
+        "event": "2024",
+        "owner_id": 1,
+        "members": {
+            "1": {
+                "id": 1, "name": "alice", "stars": 3, "local_score": 10, "global_score": 0,
+                "last_star_ts": 1733116000,
+                "completion_day_level": {
+                    "1": {
+                        "1": { "get_star_ts": 1733029500, "star_index": 0 },
+                        "2": { "get_star_ts": 1733030400, "star_index": 1 }
+                    },
+                    "2": { "1": { "get_star_ts": 1733116000, "star_index": 2 } }
+                }
+            },
+            "2": {
+                "id": 2, "name": null, "stars": 2, "local_score": 10, "global_score": 0,
+                "last_star_ts": "1733029800",
+                "completion_day_level": {
+                    "1": {
+                        "1": { "get_star_ts": 1733029260, "star_index": 0 },
+                        "2": { "get_star_ts": "1733029800", "star_index": 1 }
+                    }
+                }
+            },
+            "3": {
+                "id": 3, "name": "carol", "stars": 0, "local_score": 0, "global_score": 0,
+                "last_star_ts": 0, "completion_day_level": {}
+            }
+        }
+    }"#;
+
+    #[test]
+    fn parses_leaderboards() {
+        let leaderboard: Leaderboard = LEADERBOARD.parse().unwrap();
+        assert_eq!(leaderboard.members.len(), 3);
+
+        let anonymous = leaderboard.members.iter().find(|m| m.id == 2).unwrap();
+        assert_eq!(anonymous.display_name(), "(anonymous user #2)");
+        assert_eq!(anonymous.last_star_ts, 1_733_029_800);
+        assert_eq!(anonymous.star_ts(day!(1), 2), Some(1_733_029_800));
+        assert_eq!(anonymous.star_ts(day!(2), 1), None);
+    }
+
+    #[test]
+    fn ranks_members() {
+        let leaderboard: Leaderboard = LEADERBOARD.parse().unwrap();
+        let ids: Vec<u64> = leaderboard.ranked().iter().map(|m| m.id).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn finds_fastest_part_two() {
+        let leaderboard: Leaderboard = LEADERBOARD.parse().unwrap();
+
+        let times: Vec<(u64, u64)> = leaderboard
+            .part_two_times(year!(2024), day!(1))
+            .iter()
+            .map(|(m, elapsed)| (m.id, *elapsed))
+            .collect();
+
+        assert_eq!(times, vec![(2, 600), (1, 1200)]);
+        assert!(leaderboard.part_two_times(year!(2024), day!(2)).is_empty());
+    }
+
+    #[test]
+    fn formats_elapsed_times() {
+        assert_eq!(format_elapsed(65), "1m 05s");
+        assert_eq!(format_elapsed(3723), "1h 02m 03s");
+        assert_eq!(format_elapsed(90_061), "1d 01h 01m");
+    }
+
+    #[test]
+    fn rejects_invalid_leaderboards() {
+        assert!("{}".parse::<Leaderboard>().is_err());
+        assert!(r#"{ "members": { "1": { "id": 1 } } }"#.parse::<Leaderboard>().is_err());
+    }
+}
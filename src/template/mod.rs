@@ -1,25 +1,47 @@
-use std::{env, fs};
+use std::{env, fs, path::PathBuf};
 
 pub mod aoc_cli;
+pub mod aoc_client;
+pub mod cli;
 pub mod commands;
+pub mod registry;
 pub mod runner;
+pub mod timeout;
 
 pub use day::*;
+pub use day_set::*;
+pub use year::*;
 
+mod answers;
 mod day;
+mod day_set;
+mod examples;
+mod history;
+mod inputs;
+mod leaderboard;
+mod protocol;
 mod readme_benchmarks;
+mod readme_stars;
+mod request_gate;
 mod run_multi;
+mod stats;
+mod submissions;
 mod timings;
+mod watch;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_RED: &str = "\x1b[31m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
+pub const ANSI_YELLOW: &str = "\x1b[33m";
 
 /// Helper function that reads a text file to a string.
+/// Files are looked up in the data directory of the year the running solution belongs to, see [`Year`].
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = data_dir().join(folder).join(format!("{day}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
@@ -27,18 +49,25 @@ pub fn read_file(folder: &str, day: Day) -> String {
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
+    let filepath = data_dir().join(folder).join(format!("{day}-{part}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
+fn data_dir() -> PathBuf {
+    let cwd = env::current_dir().unwrap();
+    match Year::of_binary() {
+        Some(year) => cwd.join(year.data_dir()),
+        None => cwd.join("data"),
+    }
+}
+
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// An optional `parse = <function>` parameter runs the given function once on the input and passes
+/// a reference to its output to every part. Parsing is timed separately from the parts.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -50,19 +79,67 @@ macro_rules! solution {
     ($day:expr, 2) => {
         $crate::solution!(@impl $day, [part_two, 2]);
     };
+    ($day:expr, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, parse $parse, [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, 1, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, parse $parse, [part_one, 1]);
+    };
+    ($day:expr, 2, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, parse $parse, [part_two, 2]);
+    };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl $day:expr, $( parse $parse:expr, )? $( [$func:expr, $part:expr] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
+        /// Entry point used when the solution is compiled into the main binary.
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::registry::Solution =
+            $crate::template::registry::Solution {
+                day: DAY,
+                run: |input, is_timed, report| {
+                    use $crate::template::runner::*;
+                    let input = input.to_owned();
+                    $(
+                        let (input, parse_report) = solve_parse($parse, &input, DAY, is_timed);
+                        report(parse_report);
+                    )?
+                    $( report(solve_part($func, &input, DAY, $part, is_timed)); )*
+                },
+            };
+
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        #[allow(dead_code)]
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
+
+            if is_example_run() {
+                $crate::solution!(@example $( parse $parse, )? $( [$func, $part] )*);
+                return;
+            }
+
+            let input = read_input(DAY);
+            $( let input = run_parse($parse, &input, DAY); )?
             $( run_part($func, &input, DAY, $part); )*
         }
     };
+
+    // NOTE: parts may have separate example files, so the parse step runs for every part.
+    (@example parse $parse:expr, $( [$func:expr, $part:expr] )*) => {
+        $({
+            let input = read_example(DAY, $part);
+            let input = run_parse($parse, &input, DAY);
+            run_example_part($func, &input, DAY, $part);
+        })*
+    };
+    (@example $( [$func:expr, $part:expr] )*) => {
+        $({
+            let input = read_example(DAY, $part);
+            run_example_part($func, &input, DAY, $part);
+        })*
+    };
 }
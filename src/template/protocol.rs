@@ -0,0 +1,293 @@
+/// Machine-readable protocol used by solution binaries to report results to `run_multi`.
+/// When [`OUTPUT_FORMAT_ENV`] is set to `json`, the runner emits one JSON object per solved part instead of human output.
+use std::{collections::HashMap, env, fmt::Display, str::FromStr, time::Duration};
+
+use tinyjson::JsonValue;
+
+use crate::template::stats::{BenchStats, MemoryStats};
+use crate::template::Day;
+
+pub const OUTPUT_FORMAT_ENV: &str = "AOC_OUTPUT_FORMAT";
+
+/// Part number under which the parse step of a solution is reported.
+pub const PARSE_PART: u8 = 0;
+
+/// Returns `true` if the runner should emit JSON lines instead of human output.
+pub fn is_json_output() -> bool {
+    env::var(OUTPUT_FORMAT_ENV).is_ok_and(|x| x == "json")
+}
+
+/// Outcome of running a single part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    Solved,
+    Unsolved,
+    /// The part was cancelled after exceeding the timeout of `run_multi`.
+    TimedOut,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Solved => f.write_str("solved"),
+            Status::Unsolved => f.write_str("unsolved"),
+            Status::TimedOut => f.write_str("timed_out"),
+        }
+    }
+}
+
+impl FromStr for Status {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "solved" => Ok(Status::Solved),
+            "unsolved" => Ok(Status::Unsolved),
+            "timed_out" => Ok(Status::TimedOut),
+            s => Err(format!("Unknown part status `{s}`.")),
+        }
+    }
+}
+
+/// Result of running a single part, as emitted by a solution binary.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartReport {
+    pub day: Day,
+    pub part: u8,
+    pub status: Status,
+    pub answer: Option<String>,
+    pub stats: BenchStats,
+    /// Heap usage of the first run, only collected by builds with the `dhat-heap` feature.
+    pub memory: Option<MemoryStats>,
+}
+
+impl PartReport {
+    /// Report for a part that was cancelled after running for `timeout`.
+    pub fn timed_out(day: Day, part: u8, timeout: Duration) -> Self {
+        Self {
+            day,
+            part,
+            status: Status::TimedOut,
+            answer: None,
+            stats: BenchStats::from_nanos(timeout.as_nanos() as f64),
+            memory: None,
+        }
+    }
+
+    /// Serializes the report to a single line of JSON.
+    pub fn to_json_line(&self) -> String {
+        JsonValue::from(self)
+            .stringify()
+            .expect("part report is always serializable")
+    }
+}
+
+/// Progress of a step, emitted as a JSON line before the step reports.
+/// `run_multi` uses it to tell which step timed out and to apply its timeout to a single run of a step.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StepEvent {
+    /// The first run of a step started.
+    Started(u8),
+    /// The first run of a timed step is done and benching starts, which takes a multiple of it.
+    Benching(u8),
+}
+
+impl StepEvent {
+    /// Serializes the event to a single line of JSON.
+    pub fn to_json_line(self) -> String {
+        let (event, part) = match self {
+            StepEvent::Started(part) => ("started", part),
+            StepEvent::Benching(part) => ("benching", part),
+        };
+
+        JsonValue::from(HashMap::from([
+            ("event".to_string(), JsonValue::String(event.into())),
+            ("part".to_string(), JsonValue::Number(part.into())),
+        ]))
+        .stringify()
+        .expect("step event is always serializable")
+    }
+}
+
+impl FromStr for StepEvent {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).or(Err("not a JSON line."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected step event to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|part| *part as u8)
+            .ok_or("Expected event.part to be a number.")?;
+
+        match json.get("event").and_then(|v| v.get::<String>()) {
+            Some(event) if event == "started" => Ok(StepEvent::Started(part)),
+            Some(event) if event == "benching" => Ok(StepEvent::Benching(part)),
+            _ => Err("Expected event.event to be `started` or `benching`.".into()),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartReport> for JsonValue {
+    fn from(value: &PartReport) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(value.part.into()));
+        map.insert("status".into(), JsonValue::String(value.status.to_string()));
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(answer) => JsonValue::String(answer.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert("nanos".into(), JsonValue::Number(value.stats.median));
+        map.insert(
+            "samples".into(),
+            JsonValue::Number(value.stats.samples as f64),
+        );
+        map.insert("stats".into(), JsonValue::from(&value.stats));
+        map.insert(
+            "memory".into(),
+            match &value.memory {
+                Some(memory) => JsonValue::from(memory),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl FromStr for PartReport {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).or(Err("not a JSON line."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part report to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected report.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|part| *part as u8)
+            .ok_or("Expected report.part to be a number.")?;
+
+        let status = json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected report.status to be a string.")?
+            .parse()?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected report.answer to be null or string.")?;
+
+        let stats = json
+            .get("stats")
+            .ok_or("Expected report to have key `stats`.")
+            .map(BenchStats::try_from)??;
+
+        let memory = match json.get("memory") {
+            None | Some(JsonValue::Null) => None,
+            Some(value) => Some(MemoryStats::try_from(value)?),
+        };
+
+        Ok(PartReport {
+            day,
+            part,
+            status,
+            answer: answer.cloned(),
+            stats,
+            memory,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{PartReport, Status, StepEvent, PARSE_PART};
+    use crate::{
+        day,
+        template::stats::{BenchStats, MemoryStats},
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn roundtrips_reports() {
+        let report = PartReport {
+            day: day!(3),
+            part: 2,
+            status: Status::Solved,
+            answer: Some("@ (42) ms\nsecond line".into()),
+            stats: BenchStats::from_nanos(74.13),
+            memory: Some(MemoryStats {
+                total_bytes: 2048,
+                peak_bytes: 1024,
+                allocations: 3,
+            }),
+        };
+
+        let parsed: PartReport = report.to_json_line().parse().unwrap();
+        assert_eq!(parsed, report);
+    }
+
+    #[test]
+    fn parses_unsolved_reports() {
+        let line = r#"{"day":"01","part":1,"status":"unsolved","answer":null,"nanos":10,"samples":1,"stats":{"median":10,"mean":10,"stddev":0,"min":10,"max":10,"p95":10,"samples":1,"outliers":0}}"#;
+        let report: PartReport = line.parse().unwrap();
+        assert_eq!(report.status, Status::Unsolved);
+        assert_eq!(report.answer, None);
+        assert_eq!(report.stats.median, 10.0);
+    }
+
+    #[test]
+    fn roundtrips_timed_out_reports() {
+        let report = PartReport::timed_out(day!(11), 2, Duration::from_secs(10));
+        let parsed: PartReport = report.to_json_line().parse().unwrap();
+        assert_eq!(parsed.status, Status::TimedOut);
+        assert_eq!(parsed, report);
+    }
+
+    #[test]
+    fn rejects_human_output() {
+        assert!("Part 1: 0 (74.13ns @ 100000 samples)"
+            .parse::<PartReport>()
+            .is_err());
+    }
+
+    #[test]
+    fn roundtrips_step_events() {
+        for event in [StepEvent::Started(PARSE_PART), StepEvent::Benching(2)] {
+            assert_eq!(event.to_json_line().parse::<StepEvent>(), Ok(event));
+        }
+
+        let report = PartReport::timed_out(day!(11), 2, Duration::from_secs(10));
+        assert!(report.to_json_line().parse::<StepEvent>().is_err());
+        assert!(StepEvent::Started(1)
+            .to_json_line()
+            .parse::<PartReport>()
+            .is_err());
+    }
+}
@@ -2,8 +2,10 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
+use crate::template::protocol::PARSE_PART;
+use crate::template::stats::{format_bytes, format_nanos, BenchStats, MemoryStats};
 use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::{Day, Year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -21,17 +23,18 @@ impl From<std::io::Error> for Error {
 }
 
 pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+    pub pos_start: usize,
+    pub pos_end: usize,
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(year: Year, day: Day) -> String {
+    format!("./{}", year.bin_path(day))
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Finds a table that is enclosed by two markers. The range includes the markers.
+pub fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -55,23 +58,58 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
 fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
     let header = format!("{prefix} Benchmarks");
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    // NOTE: heap stats are only collected by `cargo time --memory`, only show the column if there are any.
+    let has_memory = timings
+        .data
+        .iter()
+        .any(|t| t.memory_1.is_some() || t.memory_2.is_some());
+
+    // NOTE: rows are keyed by year and day, the year is only shown once timings span several years.
+    let has_years = timings.data.iter().any(|t| t.year != timings.data[0].year);
+
+    let mut lines: Vec<String> = vec![MARKER.into(), header, String::new()];
+
+    if has_memory {
+        lines.push("| Day | Parse | Part 1 | Part 2 | Memory |".into());
+        lines.push("| :---: | :---: | :---: | :---: | :---:  |".into());
+    } else {
+        lines.push("| Day | Parse | Part 1 | Part 2 |".into());
+        lines.push("| :---: | :---: | :---: | :---:  |".into());
+    }
 
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
+        let path = get_path_for_bin(timing.year, timing.day);
+        let label = if has_years {
+            format!("{} Day {}", timing.year, timing.day.into_inner())
+        } else {
+            format!("Day {}", timing.day.into_inner())
+        };
+        let step = |stats: Option<BenchStats>, part: u8| {
+            if timing.timed_out == Some(part) {
+                "timed out".into()
+            } else {
+                stats.map_or_else(|| "-".into(), |stats| format_nanos(stats.median))
+            }
+        };
+
+        let mut line = format!(
+            "| [{}]({}) | `{}` | `{}` | `{}` |",
+            label,
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+            step(timing.parse, PARSE_PART),
+            step(timing.part_1, 1),
+            step(timing.part_2, 2)
+        );
+
+        if has_memory {
+            line.push_str(&format!(
+                " `{}` / `{}` |",
+                format_peak(timing.memory_1),
+                format_peak(timing.memory_2)
+            ));
+        }
+
+        lines.push(line);
     }
 
     lines.push(String::new());
@@ -81,8 +119,12 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
     lines.join("\n")
 }
 
+fn format_peak(memory: Option<MemoryStats>) -> String {
+    memory.map_or_else(|| "-".into(), |memory| format_bytes(memory.peak_bytes))
+}
+
 fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
+    let positions = locate_table(s, MARKER)?;
     let table = construct_table("##", timings, total_millis);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
@@ -100,27 +142,47 @@ pub fn update(timings: Timings) -> Result<(), Error> {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use crate::{
+        day,
+        template::stats::{BenchStats, MemoryStats},
+        template::timings::{Timing, Timings},
+        year,
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
+                    year: year!(2024),
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    parse: Some(BenchStats::from_nanos(1_000_000.0)),
+                    part_1: Some(BenchStats::from_nanos(10_000_000.0)),
+                    part_2: Some(BenchStats::from_nanos(20_000_000.0)),
+                    memory_1: None,
+                    memory_2: None,
+                    timed_out: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
+                    year: year!(2024),
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    parse: None,
+                    part_1: Some(BenchStats::from_nanos(30_000_000.0)),
+                    part_2: Some(BenchStats::from_nanos(40_000_000.0)),
+                    memory_1: None,
+                    memory_2: None,
+                    timed_out: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
+                    year: year!(2024),
                     day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
+                    parse: None,
+                    part_1: Some(BenchStats::from_nanos(40_000_000.0)),
+                    part_2: Some(BenchStats::from_nanos(50_000_000.0)),
+                    memory_1: None,
+                    memory_2: None,
+                    timed_out: None,
                     total_nanos: 9e+10,
                 },
             ],
@@ -167,11 +229,11 @@ mod tests {
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | `1.0ms` | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/02.rs) | `-` | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/04.rs) | `-` | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +242,47 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_timed_out_benchmarks() {
+        let mut timings = get_mock_timings();
+        timings.data[2].part_2 = None;
+        timings.data[2].timed_out = Some(2);
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+
+        assert!(s.contains("| [Day 4](./src/bin/04.rs) | `-` | `40.0ms` | `timed out` |"));
+    }
+
+    #[test]
+    fn format_benchmarks_of_several_years() {
+        let mut timings = get_mock_timings();
+        timings.data[2].year = year!(2023);
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+
+        assert!(s.contains("| [2024 Day 1](./src/bin/01.rs) | `1.0ms` | `10.0ms` | `20.0ms` |"));
+        assert!(s.contains("| [2023 Day 4](./src/bin/2023_04.rs) | `-` | `40.0ms` | `50.0ms` |"));
+    }
+
+    #[test]
+    fn format_benchmarks_with_memory() {
+        let mut timings = get_mock_timings();
+        timings.data[0].memory_1 = Some(MemoryStats {
+            total_bytes: 20_000,
+            peak_bytes: 12_595,
+            allocations: 8,
+        });
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+
+        assert!(s.contains("| Day | Parse | Part 1 | Part 2 | Memory |"));
+        assert!(s.contains(
+            "| [Day 1](./src/bin/01.rs) | `1.0ms` | `10.0ms` | `20.0ms` | `12.3 KiB` / `-` |"
+        ));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `-` | `30.0ms` | `40.0ms` | `-` / `-` |"));
+    }
 }
@@ -0,0 +1,282 @@
+/// Module that updates the readme with the collected stars, replacing the `advent-readme-stars` action.
+/// Stars come from the local answer and submission records, or from the progress of a member of a private leaderboard.
+use std::{collections::BTreeMap, fs};
+
+use crate::template::answers::Answers;
+use crate::template::leaderboard::Member;
+use crate::template::readme_benchmarks::{locate_table, Error};
+use crate::template::submissions::{SubmissionVerdict, Submissions};
+use crate::template::{all_days, Day, Year};
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+/// Solved parts of every day that has at least one star.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Progress {
+    pub stars: BTreeMap<Day, [bool; 2]>,
+}
+
+impl Progress {
+    /// Collects the stars of a year from the accepted answers and the submission ledger.
+    pub fn from_records(year: Year) -> Self {
+        let answers = Answers::read_from_file(year);
+        let submissions = Submissions::read_from_file(year);
+
+        let mut progress = Progress::default();
+
+        for day in all_days() {
+            for part in [1, 2] {
+                let is_solved = answers.get(day, part).is_some()
+                    || submissions
+                        .for_part(day, part)
+                        .any(|s| s.verdict == SubmissionVerdict::Correct);
+
+                if is_solved {
+                    progress.add(day, part);
+                }
+            }
+        }
+
+        progress
+    }
+
+    /// Collects the stars of a member of a private leaderboard.
+    pub fn from_member(member: &Member) -> Self {
+        let mut progress = Progress::default();
+
+        for day in all_days() {
+            for part in [1, 2] {
+                if member.star_ts(day, part).is_some() {
+                    progress.add(day, part);
+                }
+            }
+        }
+
+        progress
+    }
+
+    fn add(&mut self, day: Day, part: u8) {
+        self.stars.entry(day).or_default()[usize::from(part - 1)] = true;
+    }
+
+    pub fn count(&self) -> usize {
+        self.stars.values().flatten().filter(|x| **x).count()
+    }
+
+    /// Adds the stars of `other`, stars are never removed.
+    pub fn merge(&mut self, other: &Progress) {
+        for (day, parts) in &other.stars {
+            let stars = self.stars.entry(*day).or_default();
+            stars[0] |= parts[0];
+            stars[1] |= parts[1];
+        }
+    }
+}
+
+/// Reads the title of a puzzle from its description, e.g. `Historian Hysteria` from `## --- Day 1: Historian Hysteria ---`.
+pub fn puzzle_title(markdown: &str) -> Option<String> {
+    let heading = markdown.lines().find(|line| line.contains("--- Day "))?;
+    let (_, title) = heading.split_once(": ")?;
+    let title = title.trim().trim_end_matches("---").trim();
+
+    if title.is_empty() {
+        None
+    } else {
+        Some(title.to_string())
+    }
+}
+
+fn construct_table(year: Year, progress: &Progress, titles: &BTreeMap<Day, String>) -> String {
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        format!("## {year} Results"),
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    // NOTE: list every day up to the last one with a star, so that skipped days show up as empty rows.
+    let last_day = progress.stars.keys().last().copied();
+
+    for day in all_days().filter(|day| Some(*day) <= last_day) {
+        let n = day.into_inner();
+        let label = match titles.get(&day) {
+            Some(title) => format!("Day {n}: {title}"),
+            None => format!("Day {n}"),
+        };
+
+        let [part_1, part_2] = progress
+            .stars
+            .get(&day)
+            .copied()
+            .unwrap_or_default()
+            .map(|x| if x { "⭐" } else { " " });
+
+        lines.push(format!(
+            "| [{label}](https://adventofcode.com/{year}/day/{n}) | {part_1} | {part_2} |"
+        ));
+    }
+
+    lines.push(MARKER.into());
+
+    lines.join("\n")
+}
+
+/// Reads the stars of a year from a stars table, as written by [`construct_table`].
+fn parse_table(table: &str, year: Year) -> Progress {
+    let mut progress = Progress::default();
+    let link = format!("(https://adventofcode.com/{year}/day/");
+
+    for line in table.lines() {
+        let Some((_, rest)) = line.split_once(&link) else {
+            continue;
+        };
+
+        let Some(day) = rest
+            .split_once(')')
+            .and_then(|(day, _)| day.parse::<u8>().ok())
+            .and_then(Day::new)
+        else {
+            continue;
+        };
+
+        // cells after the label: `| ⭐ | ⭐ |`.
+        let cells: Vec<&str> = rest.split('|').skip(1).map(str::trim).collect();
+
+        for part in [1, 2] {
+            if cells.get(usize::from(part - 1)) == Some(&"⭐") {
+                progress.add(day, part);
+            }
+        }
+    }
+
+    progress
+}
+
+/// Reads the stars of a year that are currently in the stars table of the readme.
+pub fn read_stars(year: Year) -> Result<Progress, Error> {
+    let readme = String::from_utf8_lossy(&fs::read("README.md")?).to_string();
+    let positions = locate_table(&readme, MARKER)?;
+    Ok(parse_table(
+        &readme[positions.pos_start..positions.pos_end],
+        year,
+    ))
+}
+
+fn update_content(
+    s: &mut String,
+    year: Year,
+    progress: &Progress,
+    titles: &BTreeMap<Day, String>,
+) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_table(year, progress, titles);
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(())
+}
+
+/// Rebuilds the stars table of the readme, reading puzzle titles from the downloaded descriptions.
+pub fn update(year: Year, progress: &Progress) -> Result<(), Error> {
+    let titles: BTreeMap<Day, String> = all_days()
+        .filter_map(|day| {
+            let markdown = fs::read_to_string(year.puzzle_path(day)).ok()?;
+            Some((day, puzzle_title(&markdown)?))
+        })
+        .collect();
+
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, year, progress, &titles)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{construct_table, parse_table, puzzle_title, update_content, Progress, MARKER};
+    use crate::{day, year};
+
+    fn get_mock_progress() -> Progress {
+        Progress {
+            stars: BTreeMap::from([
+                (day!(1), [true, true]),
+                (day!(2), [true, false]),
+                (day!(4), [true, true]),
+            ]),
+        }
+    }
+
+    #[test]
+    fn reads_puzzle_titles() {
+        assert_eq!(
+            puzzle_title("## --- Day 1: Historian Hysteria ---\n\nThe Chief Historian..."),
+            Some("Historian Hysteria".into())
+        );
+        assert_eq!(
+            puzzle_title("\\--- Day 3: Mull It Over ---"),
+            Some("Mull It Over".into())
+        );
+        assert_eq!(puzzle_title("no heading"), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_content(&mut s, year!(2024), &get_mock_progress(), &BTreeMap::new()).unwrap();
+    }
+
+    #[test]
+    fn updates_stars_table() {
+        let mut s = format!("# readme\n{MARKER}\nold table\n{MARKER}\n\n## Benchmarks");
+        let titles = BTreeMap::from([(day!(1), "Historian Hysteria".to_string())]);
+
+        update_content(&mut s, year!(2024), &get_mock_progress(), &titles).unwrap();
+
+        let expected = [
+            "# readme",
+            MARKER,
+            "## 2024 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1: Historian Hysteria](https://adventofcode.com/2024/day/1) | ⭐ | ⭐ |",
+            "| [Day 2](https://adventofcode.com/2024/day/2) | ⭐ |   |",
+            "| [Day 3](https://adventofcode.com/2024/day/3) |   |   |",
+            "| [Day 4](https://adventofcode.com/2024/day/4) | ⭐ | ⭐ |",
+            MARKER,
+            "",
+            "## Benchmarks",
+        ]
+        .join("\n");
+
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn counts_stars() {
+        assert_eq!(get_mock_progress().count(), 5);
+        assert_eq!(Progress::default().count(), 0);
+    }
+
+    #[test]
+    fn reads_stars_from_table() {
+        let table = construct_table(year!(2024), &get_mock_progress(), &BTreeMap::new());
+        assert_eq!(parse_table(&table, year!(2024)), get_mock_progress());
+        assert_eq!(parse_table(&table, year!(2023)), Progress::default());
+    }
+
+    #[test]
+    fn merges_stars() {
+        let mut progress = get_mock_progress();
+        progress.merge(&Progress {
+            stars: BTreeMap::from([(day!(2), [false, true]), (day!(5), [true, false])]),
+        });
+
+        assert_eq!(progress.stars.get(&day!(2)), Some(&[true, true]));
+        assert_eq!(progress.stars.get(&day!(5)), Some(&[true, false]));
+        assert_eq!(progress.count(), 7);
+    }
+}
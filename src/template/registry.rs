@@ -0,0 +1,86 @@
+/// Registry of solutions that are compiled into the main binary, which allows running them without spawning cargo.
+/// Every `solution!` exports a [`Solution`], the main binary collects them via [`register_solutions!`](crate::register_solutions) and hands them to [`register`].
+use std::{fs, sync::OnceLock};
+
+use crate::template::protocol::PartReport;
+use crate::template::{Day, Year};
+
+/// Registered solutions, along with the path of their binary relative to `src/`.
+static SOLUTIONS: OnceLock<&'static [(&'static str, Solution)]> = OnceLock::new();
+
+/// Entry point of a solution that can be called in-process.
+pub struct Solution {
+    pub day: Day,
+    /// Runs all parts of the solution against the given input, passing each report to the callback.
+    /// The flag controls whether parts are benched.
+    pub run: fn(&str, bool, &mut dyn FnMut(PartReport)),
+}
+
+/// Registers the solutions available in this process. Only the first call has an effect.
+pub fn register(solutions: &'static [(&'static str, Solution)]) {
+    let _ = SOLUTIONS.set(solutions);
+}
+
+/// Returns the registered solution for a day of a year, if any.
+/// Solutions are matched by the name of their binary, which tells the years apart.
+pub fn get(year: Year, day: Day) -> Option<&'static Solution> {
+    let path = format!("bin/{}.rs", year.bin_name(day));
+
+    SOLUTIONS
+        .get()?
+        .iter()
+        .find(|(p, _)| *p == path)
+        .map(|(_, solution)| solution)
+}
+
+/// Runs a registered solution against the real input of its day.
+pub fn run(year: Year, solution: &Solution, is_timed: bool, report: &mut dyn FnMut(PartReport)) {
+    let input = fs::read_to_string(year.data_path("inputs", solution.day))
+        .expect("could not open input file");
+    (solution.run)(&input, is_timed, report);
+}
+
+/// Hidden subcommand of the main binary that runs a registered solution and prints its reports as JSON lines.
+/// Used by `run_multi` to run solutions in a child process that can be killed when a part times out.
+pub const RUN_COMMAND: &str = "__run";
+
+/// Runs a registered solution, printing every report as a JSON line. Returns `false` if the day is not registered.
+pub fn run_reporting(year: Year, day: Day, is_timed: bool) -> bool {
+    let Some(solution) = get(year, day) else {
+        return false;
+    };
+
+    run(year, solution, is_timed, &mut |report| {
+        println!("{}", report.to_json_line());
+    });
+
+    true
+}
+
+/// Includes solution binaries as modules and exposes their entry points as `SOLUTIONS`.
+/// Paths are relative to the file the macro is invoked in.
+///
+/// Solutions are only compiled in with the `registry` feature, which the `all`, `time` and `verify` aliases enable.
+/// This keeps the other commands working while a single solution does not compile.
+/// They are left out of test builds (their tests already run as part of the binaries)
+/// and of `dhat-heap` builds, where every solution brings its own global allocator.
+#[macro_export]
+macro_rules! register_solutions {
+    ($( $module:ident => $path:literal, )*) => {
+        $(
+            // helpers that are only used by a solution's tests are dead code in the main binary.
+            #[cfg(all(feature = "registry", not(any(test, feature = "dhat-heap"))))]
+            #[allow(dead_code)]
+            #[path = $path]
+            mod $module;
+        )*
+
+        /// Solutions that are compiled into this binary.
+        pub const SOLUTIONS: &[(&str, $crate::template::registry::Solution)] = &[
+            $(
+                #[cfg(all(feature = "registry", not(any(test, feature = "dhat-heap"))))]
+                ($path, $module::SOLUTION),
+            )*
+        ];
+    };
+}
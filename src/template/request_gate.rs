@@ -0,0 +1,339 @@
+/// Throttling of requests to the Advent of Code website, shared by every command and process.
+/// The time of the last request to every endpoint is persisted, so that loops and scripts can not hit
+/// the site more often than every `AOC_REQUEST_INTERVAL`. Endpoints the server asked us to back off from
+/// stay blocked until the wait is over.
+use std::{
+    collections::HashMap,
+    env,
+    fmt::Display,
+    fs,
+    str::FromStr,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::timeout;
+
+pub const REQUEST_INTERVAL_ENV: &str = "AOC_REQUEST_INTERVAL";
+
+static GATE_FILE_PATH: &str = "./data/request-gate.json";
+
+const DEFAULT_REQUEST_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Advent of Code asks to not request private leaderboards more often than every 15 minutes.
+pub const LEADERBOARD_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// Longest wait that is slept through. Requests that would need to wait longer are refused.
+const MAX_SLEEP: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Endpoint {
+    Input,
+    Puzzle,
+    Answer,
+    Leaderboard,
+}
+
+impl Endpoint {
+    fn key(self) -> &'static str {
+        match self {
+            Endpoint::Input => "input",
+            Endpoint::Puzzle => "puzzle",
+            Endpoint::Answer => "answer",
+            Endpoint::Leaderboard => "leaderboard",
+        }
+    }
+}
+
+impl Display for Endpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.key())
+    }
+}
+
+/// Timestamps of an endpoint, in milliseconds since the unix epoch.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct EndpointState {
+    last_request: u64,
+    blocked_until: u64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RequestGate {
+    /// File the state is persisted to, `None` for the in-memory gate used in tests.
+    path: Option<String>,
+    min_interval: Duration,
+    state: HashMap<String, EndpointState>,
+}
+
+impl RequestGate {
+    /// Creates the gate that is shared via `data/request-gate.json`, with the interval configured in the environment.
+    pub fn from_env() -> Self {
+        let min_interval = match env::var(REQUEST_INTERVAL_ENV) {
+            Ok(value) => timeout::parse(&value)
+                .map(Option::unwrap_or_default)
+                .unwrap_or_else(|e| {
+                    eprintln!("Ignoring {REQUEST_INTERVAL_ENV}: {e}");
+                    DEFAULT_REQUEST_INTERVAL
+                }),
+            Err(_) => DEFAULT_REQUEST_INTERVAL,
+        };
+
+        Self {
+            path: Some(GATE_FILE_PATH.into()),
+            min_interval,
+            state: HashMap::new(),
+        }
+    }
+
+    /// Creates a gate that keeps its state in memory and does not enforce an interval.
+    /// Used in tests, so they neither wait nor touch the shared state.
+    pub fn offline() -> Self {
+        Self {
+            path: None,
+            min_interval: Duration::ZERO,
+            state: HashMap::new(),
+        }
+    }
+
+    fn interval(&self, endpoint: Endpoint) -> Duration {
+        match endpoint {
+            Endpoint::Leaderboard => LEADERBOARD_INTERVAL.max(self.min_interval),
+            _ => self.min_interval,
+        }
+    }
+
+    /// Time to wait before the next request to an endpoint is allowed.
+    pub fn wait_time(&self, endpoint: Endpoint, now: u64) -> Duration {
+        let state = self.state.get(endpoint.key()).copied().unwrap_or_default();
+
+        let next_request = if state.last_request == 0 {
+            0
+        } else {
+            state.last_request + u64::try_from(self.interval(endpoint).as_millis()).unwrap_or(0)
+        };
+
+        Duration::from_millis(next_request.max(state.blocked_until).saturating_sub(now))
+    }
+
+    /// Records a request to an endpoint.
+    pub fn record(&mut self, endpoint: Endpoint, now: u64) {
+        self.state
+            .entry(endpoint.key().into())
+            .or_default()
+            .last_request = now;
+    }
+
+    /// Blocks an endpoint until the given time, e.g. after the server asked to wait.
+    pub fn block(&mut self, endpoint: Endpoint, until: u64) {
+        let state = self.state.entry(endpoint.key().into()).or_default();
+        state.blocked_until = state.blocked_until.max(until);
+    }
+
+    /// Waits until a request to an endpoint is allowed and records it.
+    /// Returns the remaining wait instead if it is too long to sleep through.
+    pub fn acquire(&mut self, endpoint: Endpoint) -> Result<(), Duration> {
+        // NOTE: other processes may have made requests in the meantime.
+        self.reload();
+
+        let wait = self.wait_time(endpoint, now_millis());
+
+        if wait > MAX_SLEEP {
+            return Err(wait);
+        }
+
+        if !wait.is_zero() {
+            println!(
+                "Waiting {:.1}s before the next {endpoint} request...",
+                wait.as_secs_f64()
+            );
+            thread::sleep(wait);
+        }
+
+        self.record(endpoint, now_millis());
+        self.store();
+        Ok(())
+    }
+
+    /// Blocks an endpoint for the given time from now and persists it.
+    pub fn back_off(&mut self, endpoint: Endpoint, wait: Duration) {
+        self.reload();
+        let wait = u64::try_from(wait.as_millis()).unwrap_or(u64::MAX);
+        self.block(endpoint, now_millis().saturating_add(wait));
+        self.store();
+    }
+
+    fn reload(&mut self) {
+        if let Some(path) = &self.path {
+            self.state = fs::read_to_string(path)
+                .map_err(|x| x.to_string())
+                .and_then(|s| parse_state(&s))
+                .unwrap_or_default();
+        }
+    }
+
+    fn store(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+
+        let json = JsonValue::from(self);
+
+        if let Err(e) = fs::File::create(path).and_then(|mut file| json.format_to(&mut file)) {
+            eprintln!("Failed to store request timestamps: {e}");
+        }
+    }
+}
+
+/// Milliseconds since the unix epoch.
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX))
+        .unwrap_or_default()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&RequestGate> for JsonValue {
+    fn from(value: &RequestGate) -> Self {
+        let map = value
+            .state
+            .iter()
+            .map(|(key, state)| {
+                #[allow(clippy::cast_precision_loss)]
+                let entry = HashMap::from([
+                    (
+                        "last_request".to_string(),
+                        JsonValue::Number(state.last_request as f64),
+                    ),
+                    (
+                        "blocked_until".to_string(),
+                        JsonValue::Number(state.blocked_until as f64),
+                    ),
+                ]);
+                (key.clone(), JsonValue::Object(entry))
+            })
+            .collect();
+
+        JsonValue::Object(map)
+    }
+}
+
+fn parse_state(s: &str) -> Result<HashMap<String, EndpointState>, String> {
+    let json = JsonValue::from_str(s).or(Err("not valid JSON file."))?;
+
+    let json = json
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or("Expected request gate to be a JSON object.")?;
+
+    json.iter()
+        .map(|(key, value)| {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let timestamp = |name: &str| {
+                value
+                    .get::<HashMap<String, JsonValue>>()
+                    .and_then(|x| x.get(name))
+                    .and_then(|x| x.get::<f64>())
+                    .map(|x| *x as u64)
+                    .ok_or(format!("Expected {key}.{name} to be a number."))
+            };
+
+            Ok((
+                key.clone(),
+                EndpointState {
+                    last_request: timestamp("last_request")?,
+                    blocked_until: timestamp("blocked_until")?,
+                },
+            ))
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use tinyjson::JsonValue;
+
+    use super::{parse_state, Endpoint, RequestGate};
+
+    fn gate(interval_secs: u64) -> RequestGate {
+        RequestGate {
+            min_interval: Duration::from_secs(interval_secs),
+            ..RequestGate::offline()
+        }
+    }
+
+    #[test]
+    fn enforces_min_interval() {
+        let mut gate = gate(5);
+        assert_eq!(gate.wait_time(Endpoint::Input, 1_000_000), Duration::ZERO);
+
+        gate.record(Endpoint::Input, 1_000_000);
+        assert_eq!(
+            gate.wait_time(Endpoint::Input, 1_002_000),
+            Duration::from_secs(3)
+        );
+        assert_eq!(gate.wait_time(Endpoint::Input, 1_006_000), Duration::ZERO);
+
+        // endpoints are throttled separately.
+        assert_eq!(gate.wait_time(Endpoint::Puzzle, 1_002_000), Duration::ZERO);
+    }
+
+    #[test]
+    fn throttles_leaderboards_longer() {
+        let mut gate = gate(5);
+        gate.record(Endpoint::Leaderboard, 1_000_000);
+        assert_eq!(
+            gate.wait_time(Endpoint::Leaderboard, 1_060_000),
+            Duration::from_secs(14 * 60)
+        );
+    }
+
+    #[test]
+    fn honours_blocks() {
+        let mut gate = gate(5);
+        gate.record(Endpoint::Answer, 1_000_000);
+        gate.block(Endpoint::Answer, 1_090_000);
+        assert_eq!(
+            gate.wait_time(Endpoint::Answer, 1_030_000),
+            Duration::from_secs(60)
+        );
+
+        // a shorter block does not lift a longer one.
+        gate.block(Endpoint::Answer, 1_040_000);
+        assert_eq!(
+            gate.wait_time(Endpoint::Answer, 1_030_000),
+            Duration::from_secs(60)
+        );
+    }
+
+    #[test]
+    fn offline_gate_does_not_wait() {
+        let mut gate = RequestGate::offline();
+        assert_eq!(gate.acquire(Endpoint::Input), Ok(()));
+        assert_eq!(gate.acquire(Endpoint::Input), Ok(()));
+    }
+
+    #[test]
+    fn refuses_long_waits() {
+        let mut gate = RequestGate::offline();
+        gate.back_off(Endpoint::Answer, Duration::from_secs(300));
+        assert!(gate.acquire(Endpoint::Answer).is_err());
+    }
+
+    #[test]
+    fn roundtrips_state() {
+        let mut gate = gate(5);
+        gate.record(Endpoint::Input, 1_000_000);
+        gate.block(Endpoint::Answer, 2_000_000);
+
+        let json = JsonValue::from(&gate).stringify().unwrap();
+        assert_eq!(parse_state(&json).unwrap(), gate.state);
+    }
+}
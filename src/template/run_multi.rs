@@ -1,14 +1,61 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::HashSet,
+    io,
+    panic::{self, AssertUnwindSafe},
+    time::Duration,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{Day, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RED, ANSI_RESET};
 
 use super::{
     all_days,
+    protocol::{PartReport, Status, PARSE_PART},
+    registry::{self, Solution},
+    runner::{format_duration, format_memory, print_result},
+    stats::format_nanos,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+/// Run the given days. With a `timeout`, every day runs in a child process that is killed once a part exceeds it.
+pub fn run_multi(
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    timeout: Option<Duration>,
+) -> Option<Timings> {
+    let reports = run_days(year, days_to_run, is_release, is_timed, timeout);
+
+    if is_timed {
+        let timings = Timings {
+            data: all_days()
+                .filter(|day| reports.iter().any(|r| r.day == *day))
+                .map(|day| {
+                    let day_reports: Vec<PartReport> =
+                        reports.iter().filter(|r| r.day == day).cloned().collect();
+                    Timing::from_reports(year, day, &day_reports)
+                })
+                .collect(),
+        };
+        let total_millis = timings.total_millis();
+        println!(
+            "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+        );
+        Some(timings)
+    } else {
+        None
+    }
+}
+
+/// Run the given days, printing their output, and return the reports of all of them.
+pub fn run_days(
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    timeout: Option<Duration>,
+) -> Vec<PartReport> {
+    let mut reports: Vec<PartReport> = vec![];
 
     let mut need_space = false;
 
@@ -24,25 +71,87 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
             println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            // prefer solutions compiled into this binary, fall back to invoking the solution binary.
+            // an in-process solution can not be cancelled, so with a timeout (the default) they run
+            // in a child process of this binary. only `--timeout none` runs them in-process.
+            let day_reports = match (registry::get(year, day), timeout) {
+                (Some(solution), None) => run_in_process(year, solution, is_timed),
+                _ => child_commands::run_solution(year, day, is_timed, is_release, false, timeout)
+                    .unwrap(),
+            };
 
-            if output.is_empty() {
+            if day_reports.is_empty() {
                 println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
             }
+
+            reports.extend(day_reports);
         });
 
-    if is_timed {
-        let timings = Timings { data: timings };
-        let total_millis = timings.total_millis();
+    reports
+}
+
+/// Run every day once in a `dhat` build of its solution binary and collect the heap stats of its parts.
+/// Heap profiling needs a global allocator, so this always invokes the solution binaries.
+pub fn run_memory(
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    timeout: Option<Duration>,
+) -> Vec<PartReport> {
+    let mut reports = vec![];
+
+    all_days()
+        .filter(|day| days_to_run.contains(day))
+        .for_each(|day| {
+            println!("\n{ANSI_BOLD}Day {day} (heap){ANSI_RESET}");
+            println!("------");
+            reports.extend(
+                child_commands::run_solution(year, day, false, false, true, timeout).unwrap(),
+            );
+        });
+
+    reports
+}
+
+/// Run a solution that is compiled into this binary.
+/// A panicking solution only aborts its own day, same as a failing child process would.
+fn run_in_process(year: Year, solution: &Solution, is_timed: bool) -> Vec<PartReport> {
+    let mut reports = vec![];
+
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| {
+        registry::run(year, solution, is_timed, &mut |report| {
+            print_report(&report);
+            reports.push(report);
+        });
+    }));
+
+    reports
+}
+
+fn print_report(report: &PartReport) {
+    let part_str = if report.part == PARSE_PART {
+        "Parse".to_string()
+    } else {
+        format!("Part {}", report.part)
+    };
+
+    if report.status == Status::TimedOut {
         println!(
-            "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+            "{part_str}: {ANSI_RED}timed out{ANSI_RESET} after {}",
+            format_nanos(report.stats.median)
         );
-        Some(timings)
+        return;
+    }
+
+    let part_str = if report.part == PARSE_PART {
+        println!("Parse:{}", format_duration(&report.stats));
+        part_str
     } else {
-        None
+        print_result(&report.answer, &part_str, &format_duration(&report.stats));
+        part_str
+    };
+
+    if let Some(memory) = report.memory {
+        println!("{part_str} heap: {}", format_memory(&memory));
     }
 }
 
@@ -60,47 +169,117 @@ impl From<std::io::Error> for Error {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(year: Year, day: Day) -> String {
+    format!("./{}", year.bin_path(day))
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as reading their reports.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{get_path_for_bin, print_report, Error};
+    use crate::template::protocol::{PartReport, StepEvent, OUTPUT_FORMAT_ENV};
+    use crate::template::{registry, Day, Year};
     use std::{
+        env,
         io::{BufRead, BufReader},
         path::Path,
         process::{Command, Stdio},
+        sync::mpsc::{self, RecvTimeoutError},
         thread,
+        time::{Duration, Instant},
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given day.
+    /// With `is_dhat`, the bin is built with the `dhat-heap` feature and reports heap stats for each part.
+    /// With a `timeout`, the child is killed once a single run of a part takes longer and the part is reported as timed out.
+    pub fn run_solution(
+        year: Year,
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        is_dhat: bool,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<PartReport>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
+        if !Path::new(&get_path_for_bin(year, day)).exists() {
             return Ok(vec![]);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let command = if registry::get(year, day).is_some() && !is_dhat {
+            // solutions compiled into this binary can be run by it directly, without invoking cargo.
+            let mut command = Command::new(env::current_exe()?);
+            command.args([
+                registry::RUN_COMMAND,
+                &day.to_string(),
+                "--year",
+                &year.to_string(),
+            ]);
+            if is_timed {
+                command.arg("--time");
+            }
+            command
+        } else {
+            let args = cargo_args(year, day, is_release, is_dhat);
+
+            if timeout.is_some() {
+                // build upfront so compilation does not count towards the timeout of the first part.
+                Command::new("cargo")
+                    .arg("build")
+                    .args(&args)
+                    .stdout(Stdio::inherit())
+                    .stderr(Stdio::inherit())
+                    .status()?;
+            }
 
-        if is_release {
-            args.push("--release");
-        }
+            let mut command = Command::new("cargo");
+            command.arg("run").args(&args);
+            if is_timed {
+                // mirror `--time` flag to child invocations.
+                command.args(["--", "--time"]);
+            }
+            command
+        };
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        collect_reports(command, day, timeout)
+    }
+
+    /// Build the solution bin for a given day and run it, so that changes to its source take effect.
+    /// Solutions compiled into this binary are run via `cargo run` as well, as this binary may be outdated.
+    /// Returns `None` if the build failed. Its errors are printed to stderr.
+    pub fn rebuild_and_run(
+        year: Year,
+        day: Day,
+        is_release: bool,
+    ) -> Result<Option<Vec<PartReport>>, Error> {
+        let args = cargo_args(year, day, is_release, false);
+
+        let status = Command::new("cargo")
+            .arg("build")
+            .args(&args)
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status()?;
+
+        if !status.success() {
+            return Ok(None);
         }
 
+        let mut command = Command::new("cargo");
+        command.arg("run").args(&args);
+        collect_reports(command, day, None).map(Some)
+    }
+
+    /// Run a solution command, forwarding its output while grabbing its part reports.
+    pub(super) fn collect_reports(
+        mut command: Command,
+        day: Day,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<PartReport>, Error> {
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward output to stdout/stderr while grabbing part reports from stdout.
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
+        let mut cmd = command
+            .env(OUTPUT_FORMAT_ENV, "json")
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
@@ -108,7 +287,7 @@ pub mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let mut reports: Vec<PartReport> = vec![];
 
         let thread = thread::spawn(move || {
             stderr.lines().for_each(|line| {
@@ -116,142 +295,134 @@ pub mod child_commands {
             });
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        // read stdout on a separate thread, so waiting for the next line can time out.
+        let (tx, rx) = mpsc::channel();
+        let reader = thread::spawn(move || {
+            for line in stdout.lines() {
+                if tx.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        // the step that is running, as announced by the solution.
+        let mut step: Option<u8> = None;
+        // the clock of a step only restarts when it starts, so output of a hanging step does not keep it alive.
+        let mut deadline = timeout.map(|timeout| Instant::now() + timeout);
+
+        loop {
+            let line = match deadline {
+                Some(deadline) => {
+                    rx.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                }
+                None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+
+            match line {
+                Ok(line) => {
+                    let line = line?;
+
+                    if let Ok(event) = line.parse::<StepEvent>() {
+                        match event {
+                            StepEvent::Started(part) => {
+                                step = Some(part);
+                                deadline = timeout.map(|timeout| Instant::now() + timeout);
+                            }
+                            // the first run finished in time. benching it takes at most ~10 runs or 1s more,
+                            // so the timeout is not applied until the step reports.
+                            StepEvent::Benching(_) => deadline = None,
+                        }
+                        continue;
+                    }
+
+                    // lines that are not part reports stem from the solution itself, e.g. debug output.
+                    match line.parse::<PartReport>() {
+                        Ok(report) => {
+                            step = None;
+                            deadline = timeout.map(|timeout| Instant::now() + timeout);
+                            print_report(&report);
+                            reports.push(report);
+                        }
+                        Err(_) => println!("{line}"),
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    cmd.kill()?;
+                    // NOTE: without an announced step, e.g. while reading the input, assume the step after the last report hung.
+                    let part = step.unwrap_or_else(|| reports.last().map_or(1, |r| r.part + 1));
+                    let report = PartReport::timed_out(day, part, timeout.unwrap_or_default());
+                    print_report(&report);
+                    reports.push(report);
+                    break;
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
         }
 
+        reader.join().unwrap();
         thread.join().unwrap();
         cmd.wait()?;
 
-        Ok(output)
+        Ok(reports)
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
+    fn cargo_args(year: Year, day: Day, is_release: bool, is_dhat: bool) -> Vec<String> {
+        let mut args = vec!["--quiet".into(), "--bin".into(), year.bin_name(day)];
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+        if is_dhat {
+            args.extend(["--profile", "dhat", "--features", "dhat-heap"].map(String::from));
+        } else if is_release {
+            args.push("--release".into());
+        }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+        args
+    }
+}
 
-                timings.total_nanos += nanos;
-            });
+/* -------------------------------------------------------------------------- */
 
-        timings
-    }
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        process::Command,
+        time::{Duration, Instant},
+    };
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
+    use super::child_commands::collect_reports;
+    use crate::day;
+    use crate::template::protocol::{Status, StepEvent, PARSE_PART};
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+    /// A child that announces a step and then runs `script`.
+    fn child(step: StepEvent, script: &str) -> Command {
+        let mut command = Command::new("sh");
+        command.args(["-c", &format!("echo '{}'; {script}", step.to_json_line())]);
+        command
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    #[test]
+    fn names_the_step_that_timed_out() {
+        let command = child(StepEvent::Started(PARSE_PART), "exec sleep 5");
+        let reports = collect_reports(command, day!(1), Some(Duration::from_millis(200))).unwrap();
+
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].part, PARSE_PART);
+        assert_eq!(reports[0].status, Status::TimedOut);
     }
 
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
-
-        #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
-        }
+    #[test]
+    fn times_out_steps_that_keep_printing() {
+        let command = child(
+            StepEvent::Started(2),
+            "while true; do echo debug; sleep 0.05; done",
+        );
 
-        #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
+        let start = Instant::now();
+        let reports = collect_reports(command, day!(1), Some(Duration::from_millis(300))).unwrap();
 
-        #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
-        }
+        assert!(start.elapsed() < Duration::from_secs(2));
+        assert_eq!(
+            reports.last().map(|r| (r.part, r.status)),
+            Some((2, Status::TimedOut))
+        );
     }
 }
@@ -1,66 +1,304 @@
 /// Encapsulates code that interacts with solution functions.
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{stdout, Write};
-use std::process::Output;
+use std::io::{self, stdout, Write};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, fs, process};
 
-use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::answers::{self, Answers};
+use crate::template::protocol::{is_json_output, PartReport, Status, StepEvent, PARSE_PART};
+use crate::template::stats::{format_bytes, format_nanos, BenchStats, MemoryStats};
+use crate::template::submissions::{self, Submission, SubmissionVerdict, Submissions};
+use crate::template::{
+    aoc_cli, data_dir, examples, history, read_file, Day, Year, ANSI_ITALIC, ANSI_RESET,
+};
+use crate::template::{ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_YELLOW};
+
+/// Share of the bench iterations that is run upfront as warmup without being measured.
+const WARMUP_DIVISOR: u128 = 10;
+
+/// Reads the input of a solution binary: the file passed via `--input <path>`, stdin for `--input -`,
+/// or the puzzle input of the day. Custom inputs are labeled in the output, so their answers are not
+/// mistaken for the ones of the puzzle input.
+pub fn read_input(day: Day) -> String {
+    let Some(source) = input_arg() else {
+        return read_file("inputs", day);
+    };
+
+    let (input, label) = if source == "-" {
+        (io::read_to_string(io::stdin()), "stdin".to_string())
+    } else {
+        (fs::read_to_string(&source), format!("\"{source}\""))
+    };
+
+    match input {
+        Ok(input) => {
+            if !is_json_output() {
+                println!("{ANSI_ITALIC}Input: {label}{ANSI_RESET}");
+            }
+            input
+        }
+        Err(e) => {
+            eprintln!("Failed to read input from {label}: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Returns `true` if the solution binary should run on the example inputs, see [`run_example_part`].
+pub fn is_example_run() -> bool {
+    env::args().any(|x| x == "--example")
+}
+
+/// Reads the example input of a part: its part file like `01-2.txt` if it exists, otherwise the shared `01.txt`.
+pub fn read_example(day: Day, part: u8) -> String {
+    let dir = data_dir().join("examples");
+    let part_path = dir.join(format!("{day}-{part}.txt"));
+
+    let path = if part_path.exists() {
+        part_path
+    } else {
+        dir.join(format!("{day}.txt"))
+    };
+
+    let label = env::current_dir()
+        .ok()
+        .and_then(|cwd| path.strip_prefix(cwd).ok())
+        .unwrap_or(&path)
+        .display()
+        .to_string();
+
+    match fs::read_to_string(&path) {
+        Ok(input) => {
+            if !is_json_output() {
+                println!("{ANSI_ITALIC}Input: \"{label}\"{ANSI_RESET}");
+            }
+            input
+        }
+        Err(e) => {
+            eprintln!("Failed to read example \"{label}\": {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Runs a part on its example input and prints its answer next to the expected one.
+/// The expected answer is read from the test of the part, or from the puzzle description if the test has none.
+pub fn run_example_part<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    day: Day,
+    part: u8,
+) {
+    if is_json_output() {
+        let report = solve_part(func, input, day, part, false);
+        println!("{}", report.to_json_line());
+        return;
+    }
+
+    let part_str = format!("Part {part}");
+
+    let (result, stats, _) = run_timed(func, input, false, |result| {
+        print_result(result, &part_str, "");
+        let _ = stdout().flush();
+    });
+
+    let verdict = match (&result, expected_example_answer(day, part)) {
+        (_, None) => format!(" {ANSI_ITALIC}no expected answer{ANSI_RESET}"),
+        (Some(result), Some(expected)) if result.to_string() == expected => {
+            format!(" {ANSI_GREEN}✓ expected {expected}{ANSI_RESET}")
+        }
+        (_, Some(expected)) => format!(" {ANSI_RED}✖ expected {expected}{ANSI_RESET}"),
+    };
+
+    print_result(
+        &result,
+        &part_str,
+        &format!("{}{verdict}", format_duration(&stats)),
+    );
+}
+
+fn expected_example_answer(day: Day, part: u8) -> Option<String> {
+    let year = Year::of_binary()?;
+
+    fs::read_to_string(year.bin_path(day))
+        .ok()
+        .and_then(|source| examples::tested_answer(&source, part))
+        .or_else(|| {
+            let markdown = fs::read_to_string(year.puzzle_path(day)).ok()?;
+            examples::expected_answers(&markdown)[usize::from(part - 1)].clone()
+        })
+}
+
+/// Value of the `--input` argument passed to the solution binary, if any.
+fn input_arg() -> Option<String> {
+    let mut args = env::args().skip_while(|x| x != "--input");
+    args.next()?;
+    args.next()
+}
 
 pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+    let is_timed = env::args().any(|x| x == "--time");
+
+    if is_json_output() {
+        let report = solve_part(func, input, day, part, is_timed);
+        println!("{}", report.to_json_line());
+        return;
+    }
+
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, stats, memory) = run_timed(func, input, is_timed, |result| {
+        print_result(result, &part_str, "");
+        if is_timed {
+            print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        }
+        let _ = stdout().flush();
+    });
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    print_result(&result, &part_str, &format_duration(&stats));
+
+    if let Some(memory) = memory {
+        println!("{part_str} heap: {}", format_memory(&memory));
+    }
 
     if let Some(result) = result {
         submit_result(result, day, part);
     }
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+/// Runs the parse step of a solution and returns its output, which is shared by all parts.
+pub fn run_parse<I: Clone, T>(func: impl Fn(I) -> T, input: I, day: Day) -> T {
+    let is_timed = env::args().any(|x| x == "--time");
+
+    if is_json_output() {
+        let (result, report) = solve_parse(func, input, day, is_timed);
+        println!("{}", report.to_json_line());
+        return result;
+    }
+
+    let (result, stats, memory) = run_timed(func, input, is_timed, |_| {
+        print!("Parse:");
+        if is_timed {
+            print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        }
+        let _ = stdout().flush();
+    });
+
+    print!("\r");
+    println!("Parse:{}", format_duration(&stats));
+
+    if let Some(memory) = memory {
+        println!("Parse heap: {}", format_memory(&memory));
+    }
+
+    result
+}
+
+/// Runs the parse step of a solution without printing anything and returns its output alongside a report.
+pub fn solve_parse<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    day: Day,
+    is_timed: bool,
+) -> (T, PartReport) {
+    announce(StepEvent::Started(PARSE_PART));
+    let (result, stats, memory) = run_timed(func, input, is_timed, |_| {
+        if is_timed {
+            announce(StepEvent::Benching(PARSE_PART));
+        }
+    });
+
+    let report = PartReport {
+        day,
+        part: PARSE_PART,
+        status: Status::Solved,
+        answer: None,
+        stats,
+        memory,
+    };
+
+    (result, report)
+}
+
+/// Runs a solution part without printing anything and returns its report.
+/// This is used when solutions are called in-process, see [`registry`](crate::template::registry).
+pub fn solve_part<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    day: Day,
+    part: u8,
+    is_timed: bool,
+) -> PartReport {
+    announce(StepEvent::Started(part));
+    let (result, stats, memory) = run_timed(func, input, is_timed, |_| {
+        if is_timed {
+            announce(StepEvent::Benching(part));
+        }
+    });
+
+    PartReport {
+        day,
+        part,
+        status: if result.is_some() {
+            Status::Solved
+        } else {
+            Status::Unsolved
+        },
+        answer: result.map(|x| x.to_string()),
+        stats,
+        memory,
+    }
+}
+
+/// Tells `run_multi` about the progress of a step, see [`StepEvent`].
+fn announce(event: StepEvent) {
+    if is_json_output() {
+        println!("{}", event.to_json_line());
+        let _ = stdout().flush();
+    }
+}
+
+/// Run a solution part. The behavior differs depending on whether we are benching:
+///  1. by default, the function is executed once.
+///  2. when timed, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    is_timed: bool,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, BenchStats, Option<MemoryStats>) {
     let timer = Instant::now();
-    let result = {
+    let (result, memory) = {
         let input = input.clone();
 
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        let result = func(input);
+        (result, MemoryStats::from_profiler())
     };
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let stats = if is_timed {
         bench(func, input, &base_time)
     } else {
-        (base_time, 1)
+        BenchStats::from_samples(&[base_time])
     };
 
-    (result, run.0, run.1)
+    (result, stats, memory)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
-
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
-
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> BenchStats {
     let bench_iterations =
         (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
+    for _ in 0..cmp::max(bench_iterations / WARMUP_DIVISOR, 1) {
+        black_box(func(black_box(input.clone())));
+    }
+
     let mut timers: Vec<Duration> = vec![];
 
     for _ in 0..bench_iterations {
@@ -71,30 +309,42 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    BenchStats::from_samples(&timers)
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
-}
+pub(crate) fn format_duration(stats: &BenchStats) -> String {
+    if stats.samples == 1 {
+        return format!(" ({})", format_nanos(stats.median));
+    }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+    let outliers = if stats.outliers > 0 {
+        format!(", {} outliers", stats.outliers)
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
-    }
+        String::new()
+    };
+
+    format!(
+        " (median {}, mean {} ± {}, min {}, p95 {}, max {}{outliers} @ {} samples)",
+        format_nanos(stats.median),
+        format_nanos(stats.mean),
+        format_nanos(stats.stddev),
+        format_nanos(stats.min),
+        format_nanos(stats.p95),
+        format_nanos(stats.max),
+        stats.samples
+    )
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+pub(crate) fn format_memory(memory: &MemoryStats) -> String {
+    format!(
+        "peak {}, total {} in {} allocations",
+        format_bytes(memory.peak_bytes),
+        format_bytes(memory.total_bytes),
+        memory.allocations
+    )
+}
+
+pub(crate) fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
@@ -131,16 +381,15 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+///  2. a session is set up, or aoc-cli is installed when using it as backend.
+///  3. the submission ledger does not rule out the answer.
+///
+/// Exits with a non-zero status unless the answer was accepted or the part was already solved.
+fn submit_result<T: Display>(result: T, day: Day, part: u8) {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
-        return None;
+        return;
     }
 
     if args.len() < 3 {
@@ -156,14 +405,84 @@ fn submit_result<T: Display>(
     };
 
     if part_submit != part {
-        return None;
+        return;
+    }
+
+    let result = result.to_string();
+
+    if input_arg().is_some() {
+        eprintln!("Refusing to submit {result}: it was not computed from the puzzle input.");
+        process::exit(1);
+    }
+
+    let Some(year) = Year::of_binary() else {
+        eprintln!("Could not tell the year of this solution. Set AOC_YEAR to a valid year.");
+        process::exit(1);
+    };
+
+    if let Err(refusal) = Submissions::read_from_file(year).check(day, part, &result) {
+        eprintln!("Refusing to submit {result}: {refusal}");
+        process::exit(1);
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    if let Err(e) = aoc_cli::check(year) {
+        eprintln!("{e}");
         process::exit(1);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    println!("Submitting result...");
+
+    let response = match aoc_cli::submit(year, day, part, &result) {
+        Ok(response) => response,
+        Err(e) => {
+            eprintln!("failed to submit result: {e}");
+            process::exit(1);
+        }
+    };
+
+    let is_solved = Answers::read_from_file(year).get(day, part).is_some();
+
+    let Some(verdict) = SubmissionVerdict::parse(&response, is_solved) else {
+        eprintln!("Could not read the verdict from the response.");
+        process::exit(1);
+    };
+
+    if verdict.is_evaluated() {
+        let submission = Submission {
+            day,
+            part,
+            answer: result.clone(),
+            verdict,
+            timestamp: history::unix_now(),
+        };
+
+        if let Err(e) = submissions::record(year, submission) {
+            eprintln!("Failed to record submission: {e}");
+        }
+    }
+
+    if verdict == SubmissionVerdict::Correct {
+        match answers::record(year, day, part, &result) {
+            Ok(()) => println!(
+                "Recorded accepted answer in \"{}/answers.json\".",
+                year.data_dir()
+            ),
+            Err(e) => eprintln!("Failed to record accepted answer: {e}"),
+        }
+    }
+
+    let color = match verdict {
+        SubmissionVerdict::Correct | SubmissionVerdict::AlreadySolved => ANSI_GREEN,
+        SubmissionVerdict::Incorrect { .. } => ANSI_RED,
+        SubmissionVerdict::RateLimited { .. } | SubmissionVerdict::WrongLevel => ANSI_YELLOW,
+    };
+
+    println!("Verdict: {ANSI_BOLD}{color}{verdict}{ANSI_RESET}");
+
+    if !matches!(
+        verdict,
+        SubmissionVerdict::Correct | SubmissionVerdict::AlreadySolved
+    ) {
+        process::exit(1);
+    }
 }
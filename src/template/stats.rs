@@ -0,0 +1,212 @@
+/// Statistics over the samples collected while benchmarking a solution part.
+use std::time::Duration;
+
+/// Samples further than this many interquartile ranges outside of the quartiles are treated as outliers.
+const OUTLIER_FENCE: f64 = 1.5;
+
+/// Summary of a benchmark run. All durations are expressed in nanoseconds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchStats {
+    pub median: f64,
+    pub mean: f64,
+    pub stddev: f64,
+    pub min: f64,
+    pub max: f64,
+    pub p95: f64,
+    /// Number of samples that were taken, including rejected outliers.
+    pub samples: u128,
+    /// Number of samples that were rejected as outliers and do not contribute to the other values.
+    pub outliers: u128,
+}
+
+impl BenchStats {
+    /// Computes statistics for a set of samples, rejecting outliers outside of Tukey's fences.
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut sorted: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        sorted.sort_unstable_by(f64::total_cmp);
+
+        let q1 = percentile(&sorted, 0.25);
+        let q3 = percentile(&sorted, 0.75);
+        let iqr = q3 - q1;
+        let (low, high) = (q1 - OUTLIER_FENCE * iqr, q3 + OUTLIER_FENCE * iqr);
+
+        let kept: Vec<f64> = sorted
+            .iter()
+            .copied()
+            .filter(|x| *x >= low && *x <= high)
+            .collect();
+
+        let count = kept.len() as f64;
+        let mean = kept.iter().sum::<f64>() / count;
+        let stddev = if kept.len() > 1 {
+            (kept.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (count - 1.0)).sqrt()
+        } else {
+            0.0
+        };
+
+        Self {
+            median: percentile(&kept, 0.5),
+            mean,
+            stddev,
+            min: kept.first().copied().unwrap_or_default(),
+            max: kept.last().copied().unwrap_or_default(),
+            p95: percentile(&kept, 0.95),
+            samples: sorted.len() as u128,
+            outliers: (sorted.len() - kept.len()) as u128,
+        }
+    }
+
+    /// Creates statistics for a single measurement.
+    pub fn from_nanos(nanos: f64) -> Self {
+        Self {
+            median: nanos,
+            mean: nanos,
+            stddev: 0.0,
+            min: nanos,
+            max: nanos,
+            p95: nanos,
+            samples: 1,
+            outliers: 0,
+        }
+    }
+}
+
+/// Heap usage of a single run, as collected by dhat.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// Bytes allocated over the whole run.
+    pub total_bytes: u64,
+    /// Maximum of bytes allocated at the same time.
+    pub peak_bytes: u64,
+    /// Number of allocations over the whole run.
+    pub allocations: u64,
+}
+
+impl MemoryStats {
+    /// Reads the stats of the active dhat heap profiler.
+    #[cfg(feature = "dhat-heap")]
+    pub fn from_profiler() -> Option<Self> {
+        let stats = dhat::HeapStats::get();
+        Some(Self {
+            total_bytes: stats.total_bytes,
+            peak_bytes: stats.max_bytes as u64,
+            allocations: stats.total_blocks,
+        })
+    }
+
+    /// Heap stats are only available with the `dhat-heap` feature.
+    #[cfg(not(feature = "dhat-heap"))]
+    pub fn from_profiler() -> Option<Self> {
+        None
+    }
+}
+
+/// Linearly interpolated percentile of an already sorted slice.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+
+    let rank = p * (sorted.len() - 1) as f64;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+/// Formats a nanosecond value the same way [`Duration`]'s debug output does, e.g. `74.1µs`.
+pub fn format_nanos(nanos: f64) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = Duration::from_nanos(nanos.round() as u64);
+    format!("{duration:.1?}")
+}
+
+/// Formats a byte count with a binary unit, e.g. `12.3 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64;
+    let mut unit = "B";
+
+    for next in UNITS {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next;
+    }
+
+    format!("{value:.1} {unit}")
+}
+
+/// Parses a duration printed by [`format_nanos`] back to nanoseconds.
+pub fn parse_nanos(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    match s.trim() {
+        s if s.ends_with("ns") => parse_to_float(s, "ns"),
+        s if s.ends_with("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+        s if s.ends_with("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+        s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
+    }
+}
+
+fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
+    s.strip_suffix(postfix)?.parse().ok()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, format_nanos, parse_nanos, BenchStats};
+    use std::time::Duration;
+
+    fn durations(nanos: &[u64]) -> Vec<Duration> {
+        nanos.iter().map(|x| Duration::from_nanos(*x)).collect()
+    }
+
+    #[test]
+    fn computes_summary() {
+        let stats = BenchStats::from_samples(&durations(&[10, 20, 30, 40, 50]));
+        assert_eq!(stats.median, 30.0);
+        assert_eq!(stats.mean, 30.0);
+        assert_eq!(stats.min, 10.0);
+        assert_eq!(stats.max, 50.0);
+        assert_eq!(stats.p95, 48.0);
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.outliers, 0);
+        assert!((stats.stddev - 15.811_388).abs() < 1.0e-6);
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let stats = BenchStats::from_samples(&durations(&[10, 11, 10, 12, 11, 10, 500]));
+        assert_eq!(stats.samples, 7);
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.max, 12.0);
+    }
+
+    #[test]
+    fn handles_single_sample() {
+        let stats = BenchStats::from_samples(&durations(&[42]));
+        assert_eq!(stats, BenchStats::from_nanos(42.0));
+    }
+
+    #[test]
+    fn formats_and_parses_nanos() {
+        assert_eq!(format_nanos(74_130.0), "74.1µs");
+        assert_eq!(parse_nanos("74.1µs"), Some(74_100.0));
+        assert_eq!(parse_nanos("74.13ns"), Some(74.13));
+        assert_eq!(parse_nanos("2s"), Some(2_000_000_000.0));
+        assert_eq!(parse_nanos("foo"), None);
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(12_595), "12.3 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
@@ -0,0 +1,586 @@
+/// Local ledger of every answer submitted to Advent of Code, together with the verdict it received.
+/// The ledger is consulted before submitting, so answers that are known to be wrong are never sent twice.
+use std::{collections::HashMap, fmt::Display, fs, io::Error, str::FromStr, time::Duration};
+
+use tinyjson::JsonValue;
+
+use crate::template::{Day, Year};
+
+static SUBMISSIONS_FILE_NAME: &str = "submissions.json";
+
+/// Verdict the server gave on a submitted answer, parsed from the text of its response.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubmissionVerdict {
+    Correct,
+    Incorrect {
+        hint: Option<Hint>,
+    },
+    /// An answer was submitted too recently. `wait` is the remaining time, if the server gave it.
+    RateLimited {
+        wait: Option<Duration>,
+    },
+    /// The part already has an accepted answer.
+    AlreadySolved,
+    /// The part cannot be submitted, e.g. part 2 before part 1 was solved.
+    WrongLevel,
+}
+
+/// Hint the server gives along with some incorrect answers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    /// Either `Correct` or `Incorrect`, other verdicts are not recorded.
+    pub verdict: SubmissionVerdict,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+/// Reason to not submit an answer.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Refusal {
+    AlreadyAccepted,
+    AlreadyRejected,
+    /// The answer is not below an answer that was too high.
+    TooHigh {
+        bound: String,
+    },
+    /// The answer is not above an answer that was too low.
+    TooLow {
+        bound: String,
+    },
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadyAccepted => write!(f, "this answer was already accepted."),
+            Refusal::AlreadyRejected => write!(f, "this answer was already rejected."),
+            Refusal::TooHigh { bound } => {
+                write!(
+                    f,
+                    "the answer must be lower than {bound}, which was too high."
+                )
+            }
+            Refusal::TooLow { bound } => {
+                write!(
+                    f,
+                    "the answer must be higher than {bound}, which was too low."
+                )
+            }
+        }
+    }
+}
+
+/// All submissions, oldest first.
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Dehydrate submissions to the JSON file of a year.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(submissions_path(year))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate submissions from the JSON file of a year. If not present, returns an empty ledger.
+    pub fn read_from_file(year: Year) -> Self {
+        fs::read_to_string(submissions_path(year))
+            .map_err(|x| x.to_string())
+            .and_then(|s| Submissions::from_str(&s))
+            .unwrap_or_default()
+    }
+
+    /// Submissions for a part, oldest first.
+    pub fn for_part(&self, day: Day, part: u8) -> impl Iterator<Item = &Submission> {
+        self.data
+            .iter()
+            .filter(move |s| s.day == day && s.part == part)
+    }
+
+    /// Tightest bounds given by `too low` and `too high` hints for a part, as `(too low, too high)`.
+    pub fn bounds(&self, day: Day, part: u8) -> (Option<&Submission>, Option<&Submission>) {
+        let with_hint = |hint: Hint| {
+            self.for_part(day, part)
+                .filter(move |s| s.verdict.hint() == Some(hint))
+                .filter_map(|s| Some((parse_number(&s.answer)?, s)))
+        };
+
+        let too_low = with_hint(Hint::TooLow).max_by_key(|(x, _)| *x).map(|x| x.1);
+        let too_high = with_hint(Hint::TooHigh)
+            .min_by_key(|(x, _)| *x)
+            .map(|x| x.1);
+
+        (too_low, too_high)
+    }
+
+    /// Checks whether an answer is worth submitting, given the previous submissions for its part.
+    pub fn check(&self, day: Day, part: u8, answer: &str) -> Result<(), Refusal> {
+        if let Some(previous) = self.for_part(day, part).find(|s| s.answer == answer) {
+            return Err(if previous.verdict == SubmissionVerdict::Correct {
+                Refusal::AlreadyAccepted
+            } else {
+                Refusal::AlreadyRejected
+            });
+        }
+
+        // NOTE: bounds only apply to numeric answers.
+        let Some(value) = parse_number(answer) else {
+            return Ok(());
+        };
+
+        let (too_low, too_high) = self.bounds(day, part);
+
+        if let Some(bound) = too_high.filter(|s| parse_number(&s.answer) <= Some(value)) {
+            return Err(Refusal::TooHigh {
+                bound: bound.answer.clone(),
+            });
+        }
+
+        if let Some(bound) = too_low.filter(|s| parse_number(&s.answer) >= Some(value)) {
+            return Err(Refusal::TooLow {
+                bound: bound.answer.clone(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Appends a submission to the ledger file.
+pub fn record(year: Year, submission: Submission) -> Result<(), Error> {
+    let mut submissions = Submissions::read_from_file(year);
+    submissions.data.push(submission);
+    submissions.store_file(year)
+}
+
+fn submissions_path(year: Year) -> String {
+    format!("{}/{SUBMISSIONS_FILE_NAME}", year.data_dir())
+}
+
+impl SubmissionVerdict {
+    /// Reads the verdict from the response to a submission. Returns `None` if the response is not recognized.
+    /// The server answers with the same message whether a part was already solved or cannot be solved yet,
+    /// so `is_solved` tells whether an accepted answer for the part is known.
+    pub fn parse(response: &str, is_solved: bool) -> Option<Self> {
+        if response.contains("That's the right answer") {
+            return Some(SubmissionVerdict::Correct);
+        }
+
+        if response.contains("That's not the right answer") {
+            let hint = if response.contains("too high") {
+                Some(Hint::TooHigh)
+            } else if response.contains("too low") {
+                Some(Hint::TooLow)
+            } else {
+                None
+            };
+
+            return Some(SubmissionVerdict::Incorrect { hint });
+        }
+
+        if response.contains("You gave an answer too recently") {
+            return Some(SubmissionVerdict::RateLimited {
+                wait: parse_wait(response),
+            });
+        }
+
+        if response.contains("You don't seem to be solving the right level") {
+            return Some(if is_solved {
+                SubmissionVerdict::AlreadySolved
+            } else {
+                SubmissionVerdict::WrongLevel
+            });
+        }
+
+        None
+    }
+
+    /// Returns `true` if the server evaluated the answer, i.e. it belongs in the ledger.
+    pub fn is_evaluated(&self) -> bool {
+        matches!(
+            self,
+            SubmissionVerdict::Correct | SubmissionVerdict::Incorrect { .. }
+        )
+    }
+
+    pub fn hint(&self) -> Option<Hint> {
+        match self {
+            SubmissionVerdict::Incorrect { hint } => *hint,
+            _ => None,
+        }
+    }
+}
+
+/// Time the server asks to wait before submitting again, either after an answer was given
+/// too recently or after too many wrong answers, e.g. `please wait 5 minutes before trying again.`
+pub fn cooldown(response: &str) -> Option<Duration> {
+    if let Some(wait) = parse_wait(response) {
+        return Some(wait);
+    }
+
+    let response = response.to_ascii_lowercase();
+    let start = response.find("please wait ")? + "please wait ".len();
+    let mut words = response[start..].split_whitespace();
+
+    let value = match words.next()? {
+        "one" | "a" | "an" => 1,
+        x => x.parse().ok()?,
+    };
+
+    match words.next()?.trim_end_matches(['.', ',']) {
+        "second" | "seconds" => Some(Duration::from_secs(value)),
+        "minute" | "minutes" => Some(Duration::from_secs(value * 60)),
+        "hour" | "hours" => Some(Duration::from_secs(value * 3600)),
+        _ => None,
+    }
+}
+
+/// Reads the remaining time from a message like `You have 1m 30s left to wait.`
+fn parse_wait(response: &str) -> Option<Duration> {
+    let start = response.find("You have ")? + "You have ".len();
+    let end = start + response[start..].find(" left to wait")?;
+
+    response[start..end]
+        .split_whitespace()
+        .map(|x| {
+            let (value, unit) = x.split_at(x.find(|c: char| !c.is_ascii_digit())?);
+            let value: u64 = value.parse().ok()?;
+            match unit {
+                "h" => Some(value * 3600),
+                "m" => Some(value * 60),
+                "s" => Some(value),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+fn parse_number(answer: &str) -> Option<i128> {
+    answer.trim().parse().ok()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl Display for SubmissionVerdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmissionVerdict::Correct => write!(f, "correct"),
+            SubmissionVerdict::Incorrect { hint: None } => write!(f, "incorrect"),
+            SubmissionVerdict::Incorrect { hint: Some(hint) } => write!(f, "incorrect ({hint})"),
+            SubmissionVerdict::RateLimited { wait: None } => write!(f, "rate limited"),
+            SubmissionVerdict::RateLimited { wait: Some(wait) } => {
+                write!(f, "rate limited ({}s left to wait)", wait.as_secs())
+            }
+            SubmissionVerdict::AlreadySolved => write!(f, "already solved"),
+            SubmissionVerdict::WrongLevel => write!(f, "wrong level"),
+        }
+    }
+}
+
+impl Display for Hint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Hint::TooHigh => f.write_str("too high"),
+            Hint::TooLow => f.write_str("too low"),
+        }
+    }
+}
+
+impl FromStr for Hint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "too high" => Ok(Hint::TooHigh),
+            "too low" => Ok(Hint::TooLow),
+            s => Err(format!("Unknown hint `{s}`.")),
+        }
+    }
+}
+
+impl From<&Submissions> for JsonValue {
+    fn from(value: &Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(value.part.into()));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(
+                if value.verdict == SubmissionVerdict::Correct {
+                    "correct"
+                } else {
+                    "incorrect"
+                }
+                .into(),
+            ),
+        );
+        map.insert(
+            "hint".into(),
+            match value.verdict.hint() {
+                Some(hint) => JsonValue::String(hint.to_string()),
+                None => JsonValue::Null,
+            },
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl FromStr for Submissions {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submissions to be a JSON object.")?;
+
+        let data = json
+            .get("data")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected submissions.data to be an array.")?;
+
+        Ok(Submissions {
+            data: data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().map(|x| *x as u64))
+                .ok_or(format!("Expected submission.{key} to be a number."))
+        };
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let hint = match json.get("hint") {
+            None | Some(JsonValue::Null) => None,
+            Some(JsonValue::String(s)) => Some(s.parse()?),
+            Some(_) => return Err("Expected submission.hint to be null or string.".into()),
+        };
+
+        let verdict = match json.get("verdict").and_then(|v| v.get::<String>()) {
+            Some(s) if s == "correct" => SubmissionVerdict::Correct,
+            Some(s) if s == "incorrect" => SubmissionVerdict::Incorrect { hint },
+            _ => return Err("Expected submission.verdict to be `correct` or `incorrect`.".into()),
+        };
+
+        Ok(Submission {
+            day,
+            part: u8::try_from(number("part")?)
+                .or(Err("Expected submission.part to be 1 or 2."))?,
+            answer: answer.clone(),
+            verdict,
+            timestamp: number("timestamp")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{cooldown, Hint, Refusal, Submission, SubmissionVerdict, Submissions};
+    use crate::day;
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    fn submission(answer: &str, verdict: SubmissionVerdict) -> Submission {
+        Submission {
+            day: day!(1),
+            part: 1,
+            answer: answer.into(),
+            verdict,
+            timestamp: 1_733_029_200,
+        }
+    }
+
+    fn incorrect(hint: Option<Hint>) -> SubmissionVerdict {
+        SubmissionVerdict::Incorrect { hint }
+    }
+
+    fn get_mock_submissions() -> Submissions {
+        Submissions {
+            data: vec![
+                submission("100", incorrect(Some(Hint::TooHigh))),
+                submission("20", incorrect(Some(Hint::TooLow))),
+                submission("90", incorrect(Some(Hint::TooHigh))),
+                submission("abc", incorrect(None)),
+            ],
+        }
+    }
+
+    #[test]
+    fn refuses_rejected_answers() {
+        let submissions = get_mock_submissions();
+        assert_eq!(
+            submissions.check(day!(1), 1, "abc"),
+            Err(Refusal::AlreadyRejected)
+        );
+        assert_eq!(submissions.check(day!(1), 2, "abc"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_answers_outside_bounds() {
+        let submissions = get_mock_submissions();
+        assert_eq!(
+            submissions.check(day!(1), 1, "95"),
+            Err(Refusal::TooHigh { bound: "90".into() })
+        );
+        assert_eq!(
+            submissions.check(day!(1), 1, "20"),
+            Err(Refusal::AlreadyRejected)
+        );
+        assert_eq!(
+            submissions.check(day!(1), 1, "-5"),
+            Err(Refusal::TooLow { bound: "20".into() })
+        );
+        assert_eq!(submissions.check(day!(1), 1, "42"), Ok(()));
+        assert_eq!(submissions.check(day!(1), 1, "xyz"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_accepted_answers() {
+        let submissions = Submissions {
+            data: vec![submission("42", SubmissionVerdict::Correct)],
+        };
+        assert_eq!(
+            submissions.check(day!(1), 1, "42"),
+            Err(Refusal::AlreadyAccepted)
+        );
+    }
+
+    #[test]
+    fn parses_verdicts() {
+        let parse = |s| SubmissionVerdict::parse(s, false);
+
+        assert_eq!(
+            parse("That's the right answer! You are one gold star closer."),
+            Some(SubmissionVerdict::Correct)
+        );
+        assert_eq!(
+            parse("That's not the right answer; your answer is too high."),
+            Some(incorrect(Some(Hint::TooHigh)))
+        );
+        assert_eq!(
+            parse("That's not the right answer; your answer is too low."),
+            Some(incorrect(Some(Hint::TooLow)))
+        );
+        assert_eq!(
+            parse("That's not the right answer. If you're stuck, make sure you're using the full input data."),
+            Some(incorrect(None))
+        );
+        assert_eq!(parse("Something unexpected."), None);
+    }
+
+    #[test]
+    fn parses_rate_limits() {
+        assert_eq!(
+            SubmissionVerdict::parse(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 1m 30s left to wait.",
+                false
+            ),
+            Some(SubmissionVerdict::RateLimited {
+                wait: Some(Duration::from_secs(90))
+            })
+        );
+        assert_eq!(
+            SubmissionVerdict::parse("You gave an answer too recently.", false),
+            Some(SubmissionVerdict::RateLimited { wait: None })
+        );
+    }
+
+    #[test]
+    fn parses_cooldowns() {
+        assert_eq!(
+            cooldown("You gave an answer too recently. You have 45s left to wait."),
+            Some(Duration::from_secs(45))
+        );
+        assert_eq!(
+            cooldown("That's not the right answer. Because you have guessed incorrectly 4 times on this puzzle, please wait 5 minutes before trying again."),
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(
+            cooldown("That's not the right answer. Please wait one minute before trying again."),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(cooldown("That's the right answer!"), None);
+    }
+
+    #[test]
+    fn parses_wrong_levels() {
+        let response = "You don't seem to be solving the right level. Did you already complete it?";
+        assert_eq!(
+            SubmissionVerdict::parse(response, false),
+            Some(SubmissionVerdict::WrongLevel)
+        );
+        assert_eq!(
+            SubmissionVerdict::parse(response, true),
+            Some(SubmissionVerdict::AlreadySolved)
+        );
+    }
+
+    #[test]
+    fn roundtrips_submissions() {
+        let submissions = get_mock_submissions();
+        let json = JsonValue::from(&submissions).stringify().unwrap();
+        let parsed: Submissions = json.parse().unwrap();
+        assert_eq!(parsed.data, submissions.data);
+    }
+}
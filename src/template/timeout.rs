@@ -0,0 +1,60 @@
+/// Wall-clock limit for a single part when running multiple days, e.g. `--timeout 10s`.
+/// The default is read from `AOC_TIMEOUT`, which is set in `.cargo/config.toml`.
+use std::{env, time::Duration};
+
+use crate::template::stats::parse_nanos;
+
+pub const TIMEOUT_ENV: &str = "AOC_TIMEOUT";
+
+/// Parses a timeout such as `500ms`, `10s` or `2m`. `0` and `none` disable the timeout.
+pub fn parse(s: &str) -> Result<Option<Duration>, String> {
+    let s = s.trim();
+
+    if s == "0" || s.eq_ignore_ascii_case("none") {
+        return Ok(None);
+    }
+
+    let nanos = match s.strip_suffix('m') {
+        Some(minutes) => minutes.parse::<f64>().ok().map(|x| x * 60_000_000_000_f64),
+        None => parse_nanos(s),
+    };
+
+    match nanos {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Some(nanos) if nanos > 0.0 => Ok(Some(Duration::from_nanos(nanos as u64))),
+        _ => Err(format!(
+            "Invalid timeout `{s}`, expected a duration like `10s`."
+        )),
+    }
+}
+
+/// Reads the default timeout from the environment. An invalid value disables the timeout.
+pub fn from_env() -> Option<Duration> {
+    let value = env::var(TIMEOUT_ENV).ok()?;
+
+    parse(&value).unwrap_or_else(|e| {
+        eprintln!("Ignoring {TIMEOUT_ENV}: {e}");
+        None
+    })
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::parse;
+    use std::time::Duration;
+
+    #[test]
+    fn parses_timeouts() {
+        assert_eq!(parse("10s"), Ok(Some(Duration::from_secs(10))));
+        assert_eq!(parse("500ms"), Ok(Some(Duration::from_millis(500))));
+        assert_eq!(parse("2m"), Ok(Some(Duration::from_secs(120))));
+        assert_eq!(parse("none"), Ok(None));
+        assert_eq!(parse("0"), Ok(None));
+    }
+
+    #[test]
+    fn rejects_invalid_timeouts() {
+        assert!(parse("soon").is_err());
+        assert!(parse("-1s").is_err());
+    }
+}
@@ -1,20 +1,115 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::protocol::{PartReport, Status, PARSE_PART};
+use crate::template::stats::{parse_nanos, BenchStats, MemoryStats};
+use crate::template::{Day, Year};
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
+    pub year: Year,
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    pub parse: Option<BenchStats>,
+    pub part_1: Option<BenchStats>,
+    pub part_2: Option<BenchStats>,
+    /// Heap usage of part 1, collected by `cargo time --memory`.
+    pub memory_1: Option<MemoryStats>,
+    /// Heap usage of part 2, collected by `cargo time --memory`.
+    pub memory_2: Option<MemoryStats>,
+    /// Step that was cancelled after exceeding the timeout, if any. Uses the part numbers of [`PartReport`].
+    pub timed_out: Option<u8>,
     pub total_nanos: f64,
 }
 
-/// Represents benchmark times for a set of days.
+impl Timing {
+    /// Collects the timings of a day from the reports emitted by its solution.
+    pub fn from_reports(year: Year, day: Day, reports: &[PartReport]) -> Self {
+        let stats_for = |part: u8| {
+            reports
+                .iter()
+                .find(|r| r.part == part && r.answer.is_some())
+                .map(|r| r.stats.clone())
+        };
+
+        let parse = reports
+            .iter()
+            .find(|r| r.part == PARSE_PART && r.status != Status::TimedOut)
+            .map(|r| r.stats.clone());
+        let part_1 = stats_for(1);
+        let part_2 = stats_for(2);
+
+        let memory_for = |part: u8| reports.iter().find(|r| r.part == part)?.memory;
+
+        let timed_out = reports
+            .iter()
+            .find(|r| r.status == Status::TimedOut)
+            .map(|r| r.part);
+
+        let mut timing = Timing {
+            year,
+            day,
+            parse,
+            part_1,
+            part_2,
+            memory_1: memory_for(1),
+            memory_2: memory_for(2),
+            timed_out,
+            total_nanos: 0.0,
+        };
+        timing.total_nanos = timing.sum_nanos();
+        timing
+    }
+
+    fn sum_nanos(&self) -> f64 {
+        [&self.parse, &self.part_1, &self.part_2]
+            .into_iter()
+            .flatten()
+            // NOTE: `sum` of an empty iterator of floats is `-0.0`, which prints as `-0.00ms`.
+            .fold(0.0, |total, x| total + x.median)
+    }
+
+    /// Merges the stored timing of the same day into this one.
+    /// Heap stats are only collected by `--memory` runs, so stored ones are kept if this timing has none.
+    /// Steps that did not run because an earlier or the same step timed out keep their stored stats.
+    fn merge_stored(mut self, stored: &Timing) -> Self {
+        self.memory_1 = self.memory_1.or(stored.memory_1);
+        self.memory_2 = self.memory_2.or(stored.memory_2);
+
+        let Some(timed_out) = self.timed_out else {
+            return self;
+        };
+
+        let steps = [
+            (PARSE_PART, &mut self.parse, &stored.parse),
+            (1, &mut self.part_1, &stored.part_1),
+            (2, &mut self.part_2, &stored.part_2),
+        ];
+
+        for (part, new, stored) in steps {
+            if part >= timed_out && new.is_none() {
+                new.clone_from(stored);
+            }
+        }
+
+        // the stored stats of a step are more useful than a timeout, which may be a one-off.
+        let has_stored_stats = match timed_out {
+            PARSE_PART => stored.parse.is_some(),
+            1 => stored.part_1.is_some(),
+            _ => stored.part_2.is_some(),
+        };
+        if has_stored_stats {
+            self.timed_out = None;
+        }
+
+        self.total_nanos = self.sum_nanos();
+        self
+    }
+}
+
+/// Represents benchmark times for a set of days, keyed by year and day.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
 pub struct Timings {
@@ -37,33 +132,128 @@ impl Timings {
             .unwrap_or_default()
     }
 
-    /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Merge two sets of timings, overwriting `self` with `other` if present, see [`Timing::merge_stored`].
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            let stored = self
+                .data
+                .iter()
+                .find(|t| t.year == timing.year && t.day == timing.day);
+
+            data.push(match stored {
+                Some(stored) => timing.clone().merge_stored(stored),
+                None => timing.clone(),
+            });
         }
 
         for timing in &self.data {
-            if !data.iter().any(|t| t.day == timing.day) {
+            if !data
+                .iter()
+                .any(|t| t.year == timing.year && t.day == timing.day)
+            {
                 data.push(timing.clone());
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| (a.year, a.day));
         Timings { data }
     }
 
+    /// Attach heap stats from reports of memory runs to the timings of the respective days.
+    /// Reports do not carry a year, so this expects the timings and reports of a run of a single year.
+    pub fn with_memory(mut self, reports: &[PartReport]) -> Self {
+        for timing in &mut self.data {
+            for report in reports.iter().filter(|r| r.day == timing.day) {
+                match report.part {
+                    1 => timing.memory_1 = report.memory,
+                    2 => timing.memory_2 = report.memory,
+                    _ => {}
+                }
+            }
+        }
+        self
+    }
+
     /// Sum up total duration of timings as millis.
     pub fn total_millis(&self) -> f64 {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
-    pub fn is_day_complete(&self, day: Day) -> bool {
+    pub fn is_day_complete(&self, year: Year, day: Day) -> bool {
+        self.data
+            .iter()
+            .any(|t| t.year == year && t.day == day && t.part_1.is_some() && t.part_2.is_some())
+    }
+
+    /// Days of a year that have timings.
+    pub fn days(&self, year: Year) -> impl Iterator<Item = Day> + '_ {
         self.data
             .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+            .filter(move |t| t.year == year)
+            .map(|t| t.day)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Change in median execution time of a single step between two sets of timings.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison {
+    pub day: Day,
+    /// Label of the step, e.g. `Part 1`.
+    pub step: &'static str,
+    pub before: f64,
+    pub after: f64,
+}
+
+impl Comparison {
+    /// Change relative to the stored timing in percent. Positive values are slowdowns.
+    /// `None` if the stored timing is zero, e.g. a legacy `0ns` timing.
+    pub fn percent_change(&self) -> Option<f64> {
+        percent_change(self.before, self.after)
+    }
+}
+
+/// Change from `before` to `after` in percent, `None` if `before` is zero.
+pub fn percent_change(before: f64, after: f64) -> Option<f64> {
+    (before > 0.0).then(|| (after - before) / before * 100_f64)
+}
+
+impl Timings {
+    /// Compare `new` timings against `self`. Steps missing from either side are skipped.
+    pub fn compare(&self, new: &Self) -> Vec<Comparison> {
+        let mut comparisons = vec![];
+
+        for timing in &new.data {
+            let Some(stored) = self
+                .data
+                .iter()
+                .find(|t| t.year == timing.year && t.day == timing.day)
+            else {
+                continue;
+            };
+
+            let steps = [
+                ("Parse", &stored.parse, &timing.parse),
+                ("Part 1", &stored.part_1, &timing.part_1),
+                ("Part 2", &stored.part_2, &timing.part_2),
+            ];
+
+            for (step, before, after) in steps {
+                if let (Some(before), Some(after)) = (before, after) {
+                    comparisons.push(Comparison {
+                        day: timing.day,
+                        step,
+                        before: before.median,
+                        after: after.median,
+                    });
+                }
+            }
+        }
+
+        comparisons
     }
 }
 
@@ -87,7 +277,14 @@ impl TryFrom<String> for Timings {
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+        Timings::try_from(&json)
+    }
+}
+
+impl TryFrom<&JsonValue> for Timings {
+    type Error = String;
 
+    fn try_from(json: &JsonValue) -> Result<Self, Self::Error> {
         let json_data = json
             .get::<HashMap<String, JsonValue>>()
             .ok_or("expected JSON document to be an object.")?
@@ -111,11 +308,23 @@ impl From<&Timing> for JsonValue {
     fn from(value: &Timing) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        map.insert(
+            "year".into(),
+            JsonValue::Number(value.year.into_inner().into()),
+        );
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
+        map.insert(
+            "parse".into(),
+            match &value.parse {
+                Some(x) => JsonValue::from(x),
+                None => JsonValue::Null,
+            },
+        );
+
+        let part_1 = value.part_1.as_ref().map(JsonValue::from);
+        let part_2 = value.part_2.as_ref().map(JsonValue::from);
 
         map.insert(
             "part_1".into(),
@@ -133,6 +342,24 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        for (key, memory) in [("memory_1", &value.memory_1), ("memory_2", &value.memory_2)] {
+            map.insert(
+                key.into(),
+                match memory {
+                    Some(x) => JsonValue::from(x),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
+        map.insert(
+            "timed_out".into(),
+            match value.timed_out {
+                Some(part) => JsonValue::Number(part.into()),
+                None => JsonValue::Null,
+            },
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -151,15 +378,41 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
+        // NOTE: timings stored before multi-year support belong to the default year.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let year = match json.get("year") {
+            None | Some(JsonValue::Null) => Year::from_env(),
+            Some(value) => value.get::<f64>().and_then(|x| Year::new(*x as u16)),
+        }
+        .ok_or("Expected timing.year to be a year.")?;
+
+        // NOTE: timings stored before parse steps were introduced do not have this key.
+        let parse = json.get("parse").map_or(Ok(None), parse_part_stats)?;
+
         let part_1 = json
             .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
+            .map(parse_part_stats)
+            .ok_or("Expected timing.part_1 to be null, string or object.")??;
 
         let part_2 = json
             .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+            .map(parse_part_stats)
+            .ok_or("Expected timing.part_2 to be null, string or object.")??;
+
+        // NOTE: heap stats are only stored by `cargo time --memory`.
+        let memory_1 = json.get("memory_1").map_or(Ok(None), parse_memory)?;
+        let memory_2 = json.get("memory_2").map_or(Ok(None), parse_memory)?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timed_out = match json.get("timed_out") {
+            None | Some(JsonValue::Null) => None,
+            Some(value) => Some(
+                value
+                    .get::<f64>()
+                    .map(|part| *part as u8)
+                    .ok_or("Expected timing.timed_out to be null or number.")?,
+            ),
+        };
 
         let total_nanos = json
             .get("total_nanos")
@@ -167,19 +420,135 @@ impl TryFrom<&JsonValue> for Timing {
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
         Ok(Timing {
+            year,
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
+            parse,
+            part_1,
+            part_2,
+            memory_1,
+            memory_2,
+            timed_out,
             total_nanos,
         })
     }
 }
 
+/// Parses the benchmark results of a part. Timings stored by older versions only hold a formatted duration string.
+fn parse_part_stats(value: &JsonValue) -> Result<Option<BenchStats>, String> {
+    match value {
+        JsonValue::Null => Ok(None),
+        JsonValue::String(s) => parse_nanos(s)
+            .map(|nanos| Some(BenchStats::from_nanos(nanos)))
+            .ok_or_else(|| format!("Could not parse duration `{s}`.")),
+        value => BenchStats::try_from(value).map(Some),
+    }
+}
+
+fn parse_memory(value: &JsonValue) -> Result<Option<MemoryStats>, String> {
+    match value {
+        JsonValue::Null => Ok(None),
+        value => MemoryStats::try_from(value).map(Some),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&BenchStats> for JsonValue {
+    fn from(value: &BenchStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("median".into(), JsonValue::Number(value.median));
+        map.insert("mean".into(), JsonValue::Number(value.mean));
+        map.insert("stddev".into(), JsonValue::Number(value.stddev));
+        map.insert("min".into(), JsonValue::Number(value.min));
+        map.insert("max".into(), JsonValue::Number(value.max));
+        map.insert("p95".into(), JsonValue::Number(value.p95));
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part timing to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected part timing.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(BenchStats {
+            median: number("median")?,
+            mean: number("mean")?,
+            stddev: number("stddev")?,
+            min: number("min")?,
+            max: number("max")?,
+            p95: number("p95")?,
+            samples: number("samples")? as u128,
+            outliers: number("outliers")? as u128,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&MemoryStats> for JsonValue {
+    fn from(value: &MemoryStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "total_bytes".into(),
+            JsonValue::Number(value.total_bytes as f64),
+        );
+        map.insert(
+            "peak_bytes".into(),
+            JsonValue::Number(value.peak_bytes as f64),
+        );
+        map.insert(
+            "allocations".into(),
+            JsonValue::Number(value.allocations as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for MemoryStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected heap stats to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().map(|x| *x as u64))
+                .ok_or(format!("Expected heap stats.{key} to be a number."))
+        };
+
+        Ok(MemoryStats {
+            total_bytes: number("total_bytes")?,
+            peak_bytes: number("peak_bytes")?,
+            allocations: number("allocations")?,
+        })
+    }
+}
+
 /* -------------------------------------------------------------------------- */
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use crate::day;
+    use crate::{day, template::stats::BenchStats, year};
 
     use super::{Timing, Timings};
 
@@ -187,21 +556,36 @@ mod tests {
         Timings {
             data: vec![
                 Timing {
+                    year: year!(2024),
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    parse: None,
+                    part_1: Some(BenchStats::from_nanos(10_000_000.0)),
+                    part_2: Some(BenchStats::from_nanos(20_000_000.0)),
+                    memory_1: None,
+                    memory_2: None,
+                    timed_out: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
+                    year: year!(2024),
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    parse: None,
+                    part_1: Some(BenchStats::from_nanos(30_000_000.0)),
+                    part_2: Some(BenchStats::from_nanos(40_000_000.0)),
+                    memory_1: None,
+                    memory_2: None,
+                    timed_out: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
+                    year: year!(2024),
                     day: day!(4),
-                    part_1: Some("40ms".into()),
+                    parse: None,
+                    part_1: Some(BenchStats::from_nanos(40_000_000.0)),
                     part_2: None,
+                    memory_1: None,
+                    memory_2: None,
+                    timed_out: None,
                     total_nanos: 4e+10,
                 },
             ],
@@ -209,20 +593,44 @@ mod tests {
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            day,
+            template::{stats::BenchStats, timings::Timings},
+        };
 
         #[test]
         fn handles_json_timings() {
-            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let json = r#"{ "data": [{ "day": "01", "part_1": { "median": 1000000, "mean": 1100000, "stddev": 50000, "min": 900000, "max": 1300000, "p95": 1250000, "samples": 100, "outliers": 2 }, "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            let part_1 = timing.part_1.as_ref().unwrap();
+            assert_eq!(part_1.median, 1_000_000_f64);
+            assert_eq!(part_1.p95, 1_250_000_f64);
+            assert_eq!(part_1.samples, 100);
+            assert_eq!(part_1.outliers, 2);
             assert_eq!(timing.part_2, None);
+            assert_eq!(timing.parse, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_parse_timings() {
+            let json = r#"{ "data": [{ "day": "01", "parse": { "median": 500, "mean": 500, "stddev": 0, "min": 500, "max": 500, "p95": 500, "samples": 1, "outliers": 0 }, "part_1": null, "part_2": null, "total_nanos": 500 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.parse, Some(BenchStats::from_nanos(500_f64)));
+        }
+
+        #[test]
+        fn handles_legacy_json_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1.5ms", "part_2": null, "total_nanos": 1500000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1, Some(BenchStats::from_nanos(1_500_000_f64)));
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -247,6 +655,7 @@ mod tests {
 
     mod serialization {
         use super::get_mock_timings;
+        use crate::template::{stats::MemoryStats, timings::Timings};
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
@@ -266,61 +675,282 @@ mod tests {
                 3
             );
         }
+
+        #[test]
+        fn roundtrips_timings() {
+            let mut timings = get_mock_timings();
+            timings.data[2].timed_out = Some(2);
+
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data.len(), 3);
+            assert_eq!(parsed.data[0].part_1, timings.data[0].part_1);
+            assert_eq!(parsed.data[0].timed_out, None);
+            assert_eq!(parsed.data[2].part_2, None);
+            assert_eq!(parsed.data[2].timed_out, Some(2));
+        }
+
+        #[test]
+        fn roundtrips_memory() {
+            let mut timings = get_mock_timings();
+            timings.data[0].memory_1 = Some(MemoryStats {
+                total_bytes: 4096,
+                peak_bytes: 2048,
+                allocations: 12,
+            });
+
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data[0].memory_1, timings.data[0].memory_1);
+            assert_eq!(parsed.data[0].memory_2, None);
+        }
     }
 
     mod is_day_complete {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::{
+                stats::BenchStats,
+                timings::{Timing, Timings},
+            },
+            year,
         };
 
         #[test]
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
+                    year: year!(2024),
                     day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
+                    parse: None,
+                    part_1: Some(BenchStats::from_nanos(1_000_000.0)),
+                    part_2: Some(BenchStats::from_nanos(2_000_000.0)),
+                    memory_1: None,
+                    memory_2: None,
+                    timed_out: None,
                     total_nanos: 3_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(year!(2024), day!(1)), true);
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
                 data: vec![Timing {
+                    year: year!(2024),
                     day: day!(1),
-                    part_1: Some("1ms".into()),
+                    parse: None,
+                    part_1: Some(BenchStats::from_nanos(1_000_000.0)),
                     part_2: None,
+                    memory_1: None,
+                    memory_2: None,
+                    timed_out: None,
                     total_nanos: 1_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(year!(2024), day!(1)), false);
         }
 
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
                 data: vec![Timing {
+                    year: year!(2024),
                     day: day!(1),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    memory_1: None,
+                    memory_2: None,
+                    timed_out: None,
                     total_nanos: 0.0,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(year!(2024), day!(1)), false);
+        }
+    }
+
+    mod from_reports {
+        use crate::{
+            day,
+            template::{
+                protocol::{PartReport, Status},
+                stats::{BenchStats, MemoryStats},
+                timings::Timing,
+            },
+            year,
+        };
+        use std::time::Duration;
+
+        #[test]
+        fn collects_solved_parts() {
+            let report = |part, answer: Option<&str>, nanos| PartReport {
+                day: day!(1),
+                part,
+                status: if answer.is_some() {
+                    Status::Solved
+                } else {
+                    Status::Unsolved
+                },
+                answer: answer.map(String::from),
+                stats: BenchStats::from_nanos(nanos),
+                memory: None,
+            };
+
+            let timing = Timing::from_reports(
+                year!(2024),
+                day!(1),
+                &[
+                    report(0, None, 5.0),
+                    report(1, Some("42"), 10.0),
+                    report(2, None, 20.0),
+                ],
+            );
+
+            assert_eq!(timing.parse, Some(BenchStats::from_nanos(5.0)));
+            assert_eq!(timing.part_1, Some(BenchStats::from_nanos(10.0)));
+            assert_eq!(timing.part_2, None);
+            assert_eq!(timing.total_nanos, 15.0);
+            assert_eq!(timing.timed_out, None);
+        }
+
+        #[test]
+        fn records_timeouts() {
+            let solved = PartReport {
+                day: day!(11),
+                part: 1,
+                status: Status::Solved,
+                answer: Some("42".into()),
+                stats: BenchStats::from_nanos(10.0),
+                memory: None,
+            };
+
+            let timing = Timing::from_reports(
+                year!(2024),
+                day!(11),
+                &[
+                    solved,
+                    PartReport::timed_out(day!(11), 2, Duration::from_secs(10)),
+                ],
+            );
+
+            assert_eq!(timing.part_1, Some(BenchStats::from_nanos(10.0)));
+            assert_eq!(timing.part_2, None);
+            assert_eq!(timing.timed_out, Some(2));
+            assert_eq!(timing.total_nanos, 10.0);
+        }
+
+        #[test]
+        fn attaches_memory() {
+            let memory = MemoryStats {
+                total_bytes: 2048,
+                peak_bytes: 1024,
+                allocations: 4,
+            };
+
+            let report = PartReport {
+                day: day!(1),
+                part: 2,
+                status: Status::Solved,
+                answer: Some("42".into()),
+                stats: BenchStats::from_nanos(1.0),
+                memory: Some(memory),
+            };
+
+            let timings = super::get_mock_timings().with_memory(&[report]);
+
+            assert_eq!(timings.data[0].memory_1, None);
+            assert_eq!(timings.data[0].memory_2, Some(memory));
+            assert_eq!(timings.data[1].memory_2, None);
+        }
+    }
+
+    mod compare {
+        use crate::{
+            day,
+            template::{
+                stats::BenchStats,
+                timings::{Comparison, Timing, Timings},
+            },
+            year,
+        };
+
+        use super::get_mock_timings;
+
+        #[test]
+        fn compares_overlapping_steps() {
+            let timings = get_mock_timings();
+            let new = Timings {
+                data: vec![
+                    Timing {
+                        year: year!(2024),
+                        day: day!(1),
+                        parse: None,
+                        part_1: Some(BenchStats::from_nanos(15_000_000.0)),
+                        part_2: Some(BenchStats::from_nanos(10_000_000.0)),
+                        memory_1: None,
+                        memory_2: None,
+                        timed_out: None,
+                        total_nanos: 25_000_000.0,
+                    },
+                    Timing {
+                        year: year!(2024),
+                        day: day!(3),
+                        parse: None,
+                        part_1: Some(BenchStats::from_nanos(1.0)),
+                        part_2: None,
+                        memory_1: None,
+                        memory_2: None,
+                        timed_out: None,
+                        total_nanos: 1.0,
+                    },
+                    Timing {
+                        year: year!(2024),
+                        day: day!(4),
+                        parse: None,
+                        part_1: None,
+                        part_2: Some(BenchStats::from_nanos(1.0)),
+                        memory_1: None,
+                        memory_2: None,
+                        timed_out: None,
+                        total_nanos: 1.0,
+                    },
+                ],
+            };
+
+            let comparisons = timings.compare(&new);
+            assert_eq!(comparisons.len(), 2);
+            assert_eq!(comparisons[0].step, "Part 1");
+            assert_eq!(comparisons[0].percent_change(), Some(50.0));
+            assert_eq!(comparisons[1].step, "Part 2");
+            assert_eq!(comparisons[1].percent_change(), Some(-50.0));
+        }
+
+        #[test]
+        fn skips_change_of_zero_timings() {
+            let comparison = Comparison {
+                day: day!(1),
+                step: "Part 1",
+                before: 0.0,
+                after: 10.0,
+            };
+            assert_eq!(comparison.percent_change(), None);
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::{
+                protocol::PARSE_PART,
+                stats::{BenchStats, MemoryStats},
+                timings::{Timing, Timings},
+            },
+            year,
         };
 
         use super::get_mock_timings;
@@ -330,9 +960,14 @@ mod tests {
             let timings = get_mock_timings();
             let other = Timings {
                 data: vec![Timing {
+                    year: year!(2024),
                     day: day!(3),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    memory_1: None,
+                    memory_2: None,
+                    timed_out: None,
                     total_nanos: 0_f64,
                 }],
             };
@@ -350,9 +985,14 @@ mod tests {
 
             let other = Timings {
                 data: vec![Timing {
+                    year: year!(2024),
                     day: day!(2),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    memory_1: None,
+                    memory_2: None,
+                    timed_out: None,
                     total_nanos: 0_f64,
                 }],
             };
@@ -380,5 +1020,70 @@ mod tests {
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 3);
         }
+
+        #[test]
+        fn keeps_stored_memory() {
+            let memory = MemoryStats {
+                total_bytes: 4096,
+                peak_bytes: 2048,
+                allocations: 12,
+            };
+
+            let mut stored = get_mock_timings();
+            stored.data[0].memory_1 = Some(memory);
+
+            let mut new = get_mock_timings();
+            new.data.truncate(1);
+
+            let merged = stored.merge(&new);
+            assert_eq!(merged.data.len(), 3);
+            assert_eq!(merged.data[0].day, day!(1));
+            assert_eq!(merged.data[0].memory_1, Some(memory));
+            assert_eq!(merged.data[0].memory_2, None);
+        }
+
+        #[test]
+        fn keeps_stored_stats_of_timed_out_steps() {
+            let stored = get_mock_timings();
+
+            let mut new = get_mock_timings();
+            new.data.truncate(1);
+            new.data[0].part_2 = None;
+            new.data[0].timed_out = Some(2);
+
+            let merged = stored.merge(&new);
+            assert_eq!(merged.data[0].part_2, stored.data[0].part_2);
+            assert_eq!(merged.data[0].timed_out, None);
+            assert_eq!(merged.data[0].total_nanos, 30_000_000.0);
+
+            // without stored stats, the timeout is kept.
+            let stored = Timings {
+                data: vec![Timing {
+                    part_2: None,
+                    ..stored.data[0].clone()
+                }],
+            };
+            let merged = stored.merge(&new);
+            assert_eq!(merged.data[0].part_2, None);
+            assert_eq!(merged.data[0].timed_out, Some(2));
+        }
+
+        #[test]
+        fn keeps_stored_stats_of_timed_out_parse() {
+            let mut stored = get_mock_timings();
+            stored.data[0].parse = Some(BenchStats::from_nanos(5_000_000.0));
+
+            let mut new = get_mock_timings();
+            new.data.truncate(1);
+            new.data[0].part_1 = None;
+            new.data[0].part_2 = None;
+            new.data[0].timed_out = Some(PARSE_PART);
+
+            let merged = stored.merge(&new);
+            assert_eq!(merged.data[0].parse, stored.data[0].parse);
+            assert_eq!(merged.data[0].part_1, stored.data[0].part_1);
+            assert_eq!(merged.data[0].part_2, stored.data[0].part_2);
+            assert_eq!(merged.data[0].timed_out, None);
+        }
     }
 }
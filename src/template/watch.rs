@@ -0,0 +1,166 @@
+/// File watching for `solve --watch`. Files are polled for changes of their modification time,
+/// which needs no platform-specific APIs and is plenty fast for a handful of files.
+use std::{collections::BTreeMap, fs, time::SystemTime};
+
+use crate::template::examples::example_paths;
+use crate::template::protocol::{PartReport, PARSE_PART};
+use crate::template::{Day, Year};
+
+/// Files that affect the answers of a day: its solution, the shared library, its input and its examples.
+pub fn watched_paths(year: Year, day: Day) -> Vec<String> {
+    let mut paths = vec![
+        year.bin_path(day),
+        "src/lib.rs".into(),
+        year.data_path("inputs", day),
+    ];
+
+    paths.extend(
+        example_paths(year, day, false)
+            .into_iter()
+            .map(|(_, path)| path),
+    );
+    paths
+}
+
+/// Modification times of a set of files, `None` for files that do not exist.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Snapshot(BTreeMap<String, Option<SystemTime>>);
+
+impl Snapshot {
+    pub fn take(paths: &[String]) -> Self {
+        Self(
+            paths
+                .iter()
+                .map(|path| {
+                    let modified = fs::metadata(path).and_then(|m| m.modified()).ok();
+                    (path.clone(), modified)
+                })
+                .collect(),
+        )
+    }
+
+    /// Paths that were created, removed or modified since `previous`.
+    pub fn changed_since(&self, previous: &Snapshot) -> Vec<&str> {
+        self.0
+            .iter()
+            .filter(|(path, modified)| previous.0.get(*path) != Some(modified))
+            .map(|(path, _)| path.as_str())
+            .collect()
+    }
+}
+
+/// Change of the answer of a part between two runs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AnswerChange {
+    Unchanged(Option<String>),
+    Changed {
+        before: Option<String>,
+        after: Option<String>,
+    },
+    /// The part did not report in the previous run, e.g. because it did not compile.
+    New(Option<String>),
+}
+
+/// Compares the answers of two runs of a day, by part.
+pub fn diff_answers(previous: &[PartReport], current: &[PartReport]) -> Vec<(u8, AnswerChange)> {
+    current
+        .iter()
+        .filter(|report| report.part != PARSE_PART)
+        .map(|report| {
+            let after = report.answer.clone();
+
+            let change = match previous.iter().find(|r| r.part == report.part) {
+                None => AnswerChange::New(after),
+                Some(before) if before.answer == after => AnswerChange::Unchanged(after),
+                Some(before) => AnswerChange::Changed {
+                    before: before.answer.clone(),
+                    after,
+                },
+            };
+
+            (report.part, change)
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        collections::BTreeMap,
+        time::{Duration, SystemTime},
+    };
+
+    use super::{diff_answers, watched_paths, AnswerChange, Snapshot};
+    use crate::template::protocol::{PartReport, Status, PARSE_PART};
+    use crate::template::stats::BenchStats;
+    use crate::{day, year};
+
+    fn report(part: u8, answer: Option<&str>) -> PartReport {
+        PartReport {
+            day: day!(1),
+            part,
+            status: Status::Solved,
+            answer: answer.map(String::from),
+            stats: BenchStats::from_nanos(1.0),
+            memory: None,
+        }
+    }
+
+    #[test]
+    fn diffs_answers_by_part() {
+        let previous = [report(1, Some("42")), report(2, Some("7"))];
+        let current = [
+            report(PARSE_PART, None),
+            report(1, Some("42")),
+            report(2, Some("8")),
+        ];
+
+        assert_eq!(
+            diff_answers(&previous, &current),
+            vec![
+                (1, AnswerChange::Unchanged(Some("42".into()))),
+                (
+                    2,
+                    AnswerChange::Changed {
+                        before: Some("7".into()),
+                        after: Some("8".into())
+                    }
+                ),
+            ]
+        );
+
+        assert_eq!(
+            diff_answers(&[], &[report(1, None)]),
+            vec![(1, AnswerChange::New(None))]
+        );
+    }
+
+    #[test]
+    fn detects_changed_files() {
+        let t = SystemTime::UNIX_EPOCH;
+        let previous = Snapshot(BTreeMap::from([
+            ("a".to_string(), Some(t)),
+            ("b".to_string(), None),
+            ("c".to_string(), Some(t)),
+        ]));
+        let current = Snapshot(BTreeMap::from([
+            ("a".to_string(), Some(t)),
+            ("b".to_string(), Some(t)),
+            ("c".to_string(), Some(t + Duration::from_secs(1))),
+        ]));
+
+        assert_eq!(current.changed_since(&previous), vec!["b", "c"]);
+        assert!(current.changed_since(&current).is_empty());
+    }
+
+    #[test]
+    fn watches_solution_input_and_examples() {
+        let paths = watched_paths(year!(2024), day!(6));
+        assert!(paths.contains(&"src/lib.rs".to_string()));
+        assert!(paths.iter().any(|p| p.ends_with("inputs/06.txt")));
+        assert!(paths.iter().any(|p| p.ends_with("examples/06-2.txt")));
+        assert!(paths.iter().any(|p| p.ends_with("06.rs")));
+    }
+}
@@ -0,0 +1,227 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::path::Path;
+use std::str::FromStr;
+use std::{env, fs};
+
+use crate::template::day::SERVER_UTC_OFFSET;
+use crate::template::Day;
+
+/// Environment variable holding the default year, set in `.cargo/config.toml`.
+pub const YEAR_ENV: &str = "AOC_YEAR";
+
+/// A valid year of advent of code (i.e. an integer starting at 2015).
+///
+/// # Layout
+/// Every year keeps its files in `data/<year>/` and its solutions in `src/bin/<year>_<day>.rs`.
+/// For backward compatibility, the default year (`AOC_YEAR`) uses the legacy single-year layout
+/// of `data/` and `src/bin/<day>.rs` unless a `data/<year>/` directory exists.
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year = Year::new(2024).unwrap();
+/// assert_eq!(year.to_string(), "2024")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's in the valid range,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if year < 2015 {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// Returns the default year configured via `AOC_YEAR`, if valid.
+    pub fn from_env() -> Option<Self> {
+        env::var(YEAR_ENV).ok()?.parse().ok()
+    }
+
+    /// Returns the year of the running solution binary. Binaries of the year-scoped layout
+    /// carry their year in their name (e.g. `2024_06`), legacy binaries belong to the default year.
+    pub fn of_binary() -> Option<Self> {
+        env::current_exe()
+            .ok()
+            .and_then(|exe| {
+                let name = exe.file_stem()?.to_string_lossy().to_string();
+                // test binaries carry a hash suffix, e.g. `2024_06-1a2b3c`.
+                let name = name.split('-').next()?;
+                parse_bin_name(name).and_then(|(year, _)| year)
+            })
+            .or_else(Self::from_env)
+    }
+
+    /// Returns `true` if this year uses the legacy single-year layout.
+    pub fn is_legacy(self) -> bool {
+        Some(self) == Self::from_env() && !Path::new(&format!("data/{self}")).is_dir()
+    }
+
+    /// Directory that holds inputs, examples, puzzles and records of this year.
+    pub fn data_dir(self) -> String {
+        data_dir(self, self.is_legacy())
+    }
+
+    /// Name of the solution binary of a day.
+    pub fn bin_name(self, day: Day) -> String {
+        bin_name(self, day, self.is_legacy())
+    }
+
+    /// Path of a data file of a day, e.g. `data/2024/inputs/06.txt`.
+    pub fn data_path(self, folder: &str, day: Day) -> String {
+        format!("{}/{folder}/{day}.txt", self.data_dir())
+    }
+
+    /// Path of the puzzle description of a day.
+    pub fn puzzle_path(self, day: Day) -> String {
+        format!("{}/puzzles/{day}.md", self.data_dir())
+    }
+
+    /// Path of the solution binary of a day.
+    pub fn bin_path(self, day: Day) -> String {
+        format!("src/bin/{}.rs", self.bin_name(day))
+    }
+
+    /// Unix timestamp at which the puzzle of a day unlocks, i.e. midnight in the server's timezone.
+    pub fn unlock_timestamp(self, day: Day) -> u64 {
+        // days since the unix epoch of december 1st, see: http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+        let year = u64::from(self.0);
+        let era = year / 400;
+        let yoe = year % 400;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + 275;
+        let days = era * 146_097 + doe - 719_468 + u64::from(day.into_inner()) - 1;
+
+        (days * 86_400).saturating_add_signed(-i64::from(SERVER_UTC_OFFSET) * 3600)
+    }
+
+    /// Creates the data directories of a year if they do not exist.
+    pub fn create_data_dirs(self) -> std::io::Result<()> {
+        for folder in ["inputs", "examples", "puzzles"] {
+            fs::create_dir_all(format!("{}/{folder}", self.data_dir()))?;
+        }
+        Ok(())
+    }
+}
+
+fn data_dir(year: Year, is_legacy: bool) -> String {
+    if is_legacy {
+        "data".into()
+    } else {
+        format!("data/{year}")
+    }
+}
+
+fn bin_name(year: Year, day: Day, is_legacy: bool) -> String {
+    if is_legacy {
+        day.to_string()
+    } else {
+        format!("{year}_{day}")
+    }
+}
+
+/// Parses the name of a solution binary into its year, if year-scoped, and day.
+pub fn parse_bin_name(name: &str) -> Option<(Option<Year>, Day)> {
+    match name.split_once('_') {
+        Some((year, day)) => Some((Some(year.parse().ok()?), day.parse().ok()?)),
+        None => Some((None, name.parse().ok()?)),
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year starting at 2015")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= 2015,
+            concat!(
+                "invalid year `",
+                $year,
+                "`, expecting a value starting at 2015"
+            ),
+        );
+        $crate::template::Year::__new_unchecked($year)
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{bin_name, data_dir, parse_bin_name, Year};
+    use crate::day;
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2024".parse::<Year>().unwrap(), Year(2024));
+        assert!("2014".parse::<Year>().is_err());
+        assert!("24".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn builds_paths() {
+        let year = Year(2023);
+        assert_eq!(data_dir(year, false), "data/2023");
+        assert_eq!(data_dir(year, true), "data");
+        assert_eq!(bin_name(year, day!(6), false), "2023_06");
+        assert_eq!(bin_name(year, day!(6), true), "06");
+    }
+
+    #[test]
+    fn computes_unlock_timestamps() {
+        // 2024-12-01 05:00 UTC
+        assert_eq!(Year(2024).unlock_timestamp(day!(1)), 1_733_029_200);
+        // 2015-12-25 05:00 UTC
+        assert_eq!(Year(2015).unlock_timestamp(day!(25)), 1_451_019_600);
+    }
+
+    #[test]
+    fn parses_bin_names() {
+        assert_eq!(parse_bin_name("2023_06"), Some((Some(Year(2023)), day!(6))));
+        assert_eq!(parse_bin_name("06"), Some((None, day!(6))));
+        assert_eq!(parse_bin_name("advent_of_code"), None);
+    }
+}